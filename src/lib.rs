@@ -7,8 +7,14 @@
 //! All data in this crate has been directly transposed from the [datasheet](https://www.revrobotics.com/content/docs/REV-11-1105-UM.pdf)'s color table.
 
 #![no_std]
+#![allow(clippy::needless_return)]
 
 extern crate num;
+use core::convert::TryFrom;
+use core::fmt;
+#[cfg(feature = "names")]
+use core::str::FromStr;
+use num::traits::float::FloatCore;
 use num::{Num, NumCast};
 
 /// Expression of each valid driver colour mode as a value from `0..200`.
@@ -20,7 +26,29 @@ use num::{Num, NumCast};
 /// be converted to the correct duty cycle format before use
 ///
 /// These values are transcribed from the [user manual](https://www.revrobotics.com/content/docs/REV-11-1105-UM.pdf)
-#[derive(Debug, Clone, Copy)]
+///
+/// The enum is `#[repr(u8)]`, so each pattern is laid out as its raw value
+///
+/// Patterns are ordered by their raw datasheet value (which is also their duty cycle
+/// order), not alphabetically
+///
+/// With the `serde` feature enabled, patterns are serialized by their variant name (eg. `"RainbowOcean"`).
+/// Self-describing formats (eg. JSON) can deserialize either the variant name or the raw datasheet value
+/// (eg. `"RainbowOcean"` or `5`)
+///
+/// With the `defmt` feature enabled, patterns implement `defmt::Format` and are logged by their variant name:
+///
+/// ```ignore
+/// defmt::info!("pattern={}", Pattern::RainbowOcean); // pattern=RainbowOcean
+/// ```
+///
+/// Without the (default) `names` feature, no pattern names are compiled in, and `Debug`
+/// prints the raw value instead (eg. `Pattern(5)`)
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "names", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
 pub enum Pattern {
     Rainbow = 1,
     RainbowParty = 3,
@@ -122,46 +150,3467 @@ pub enum Pattern {
     Black = 199,
 }
 
+/// Family a [`Pattern`] belongs to, following the groupings of the datasheet's color table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Rainbow,
+    Confetti,
+    Shot,
+    Sinelon,
+    Bpm,
+    Fire,
+    Twinkles,
+    Waves,
+    Larson,
+    Chase,
+    Heartbeat,
+    Breath,
+    Strobe,
+    /// Patterns driven by the user-configured Color 1
+    Color1,
+    /// Patterns driven by the user-configured Color 2
+    Color2,
+    /// Patterns driven by both user-configured colors
+    Color1And2,
+    /// Fixed solid colors (`HotPink` through `Black`)
+    SolidColor,
+}
+
+impl Category {
+    /// Get the first pattern in this category, in declaration order
+    pub fn first_pattern(&self) -> Pattern {
+        return Pattern::in_category(*self).next().unwrap();
+    }
+}
+
+/// Motion style of a [`Pattern`], independent of its colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// No motion (includes the Color 1 and 2 setup pattern)
+    Solid,
+    Rainbow,
+    Glitter,
+    Confetti,
+    Shot,
+    Sinelon,
+    Bpm,
+    Fire,
+    Twinkles,
+    Waves,
+    Larson,
+    Chase,
+    Heartbeat,
+    Breath,
+    Strobe,
+    /// End to end blends
+    Blend,
+    Sparkle,
+    Gradient,
+}
+
+/// Color palette used by a [`Pattern`], for patterns the datasheet lists with a named palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Palette {
+    Rainbow,
+    Party,
+    Ocean,
+    Lava,
+    Forest,
+    /// The pattern does not use one of the named palettes
+    None,
+}
+
+/// One of the two user-configurable colors on the driver.
+///
+/// These are set on the device itself (with its Color 1 and Color 2 knobs) before
+/// selecting a pattern that depends on them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSlot {
+    Color1,
+    Color2,
+}
+
+/// Rounding applied when converting a duty cycle to an integer with [`Pattern::as_duty_rounded()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round to the nearest integer, with halfway values rounded away from zero
+    Nearest,
+    /// Round down
+    Floor,
+    /// Round up
+    Ceil,
+}
+
+/// Every pattern, in declaration order
+const PATTERNS: [Pattern; 98] = [
+    Pattern::Rainbow,
+    Pattern::RainbowParty,
+    Pattern::RainbowOcean,
+    Pattern::RainbowLava,
+    Pattern::RainbowForest,
+    Pattern::RainbowGlitter,
+    Pattern::Confetti,
+    Pattern::RedShot,
+    Pattern::BlueShot,
+    Pattern::WhiteShot,
+    Pattern::SinelonRainbow,
+    Pattern::SinelonParty,
+    Pattern::SinelonOcean,
+    Pattern::SinelonLava,
+    Pattern::SinelonForest,
+    Pattern::BpmRainbow,
+    Pattern::BpmOcean,
+    Pattern::BpmLava,
+    Pattern::BpmForest,
+    Pattern::FireMedium,
+    Pattern::FireLarge,
+    Pattern::TwinklesRainbow,
+    Pattern::TwinklesParty,
+    Pattern::TwinklesOcean,
+    Pattern::TwinklesLava,
+    Pattern::TwinklesForest,
+    Pattern::WavesRainbow,
+    Pattern::WavesParty,
+    Pattern::WavesOcean,
+    Pattern::WavesLava,
+    Pattern::WavesForest,
+    Pattern::LarsonRed,
+    Pattern::LarsonGray,
+    Pattern::ChaseRed,
+    Pattern::ChaseBlue,
+    Pattern::ChaseGray,
+    Pattern::HeartbeatRed,
+    Pattern::HeartbeatBlue,
+    Pattern::HeartbeatWhite,
+    Pattern::HeartbeatGray,
+    Pattern::BreathRed,
+    Pattern::BreathBlue,
+    Pattern::BreathGray,
+    Pattern::StrobeBlue,
+    Pattern::StrobeGold,
+    Pattern::StrobeWhite,
+    Pattern::Color1BlendToBlack,
+    Pattern::Color1Larson,
+    Pattern::Color1Chase,
+    Pattern::Color1HeartbeatSlow,
+    Pattern::Color1HeartbeatMedium,
+    Pattern::Color1HeartbeatFast,
+    Pattern::Color1BreathSlow,
+    Pattern::Color1BreathFast,
+    Pattern::Color1Shot,
+    Pattern::Color1Strobe,
+    Pattern::Color2BlendToBlack,
+    Pattern::Color2Larson,
+    Pattern::Color2Chase,
+    Pattern::Color2HeartbeatSlow,
+    Pattern::Color2HeartbeatMedium,
+    Pattern::Color2HeartbeatFast,
+    Pattern::Color2BreathSlow,
+    Pattern::Color2BreathFast,
+    Pattern::Color2Shot,
+    Pattern::Color2Strobe,
+    Pattern::Sparkle1On2,
+    Pattern::Sparkle2On1,
+    Pattern::Gradient1And2,
+    Pattern::Bpm1And2,
+    Pattern::EndBlend1And2,
+    Pattern::EndBlend,
+    Pattern::Color1And2NoBlend,
+    Pattern::Twinkle1And2,
+    Pattern::Waves1And2,
+    Pattern::Sinelon1And2,
+    Pattern::HotPink,
+    Pattern::DarkRed,
+    Pattern::Red,
+    Pattern::RedOrange,
+    Pattern::Orange,
+    Pattern::Gold,
+    Pattern::Yellow,
+    Pattern::LawnGreen,
+    Pattern::Lime,
+    Pattern::DarkGreen,
+    Pattern::Green,
+    Pattern::BlueGreen,
+    Pattern::Aqua,
+    Pattern::SkyBlue,
+    Pattern::DarkBlue,
+    Pattern::Blue,
+    Pattern::BlueViolet,
+    Pattern::Violet,
+    Pattern::White,
+    Pattern::Gray,
+    Pattern::DarkGray,
+    Pattern::Black,
+];
+
+/// Datasheet entry for a single [`Pattern`].
+///
+/// Returned by [`Pattern::info()`], and collected for every pattern in [`Pattern::TABLE`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatternInfo {
+    pub pattern: Pattern,
+    /// See [`Pattern::name()`]. Requires the `names` feature
+    #[cfg(feature = "names")]
+    pub name: &'static str,
+    /// See [`Pattern::display_name()`]. Requires the `names` feature
+    #[cfg(feature = "names")]
+    pub display_name: &'static str,
+    /// See [`Pattern::as_raw()`]
+    pub raw: u8,
+    /// See [`Pattern::as_pulse_width_us()`]
+    pub pulse_width_us: u16,
+}
+
+/// Pick an index from `0..len` without modulo bias
+#[cfg(feature = "rand")]
+fn random_index<R: rand_core::RngCore>(rng: &mut R, len: usize) -> usize {
+    let len = len as u32;
+    let zone = (u32::MAX / len) * len;
+    loop {
+        let value = rng.next_u32();
+        if value < zone {
+            return (value % len) as usize;
+        }
+    }
+}
+
+/// Lookup from every possible raw value to its pattern, built from [`Pattern::RAW_VALUES`].
+///
+/// Indexing this is considerably faster than either matching on or binary searching the raw value
+const DECODE_TABLE: [Option<Pattern>; 256] = {
+    let mut table = [None; 256];
+    let mut i = 0;
+    while i < Pattern::COUNT {
+        table[Pattern::RAW_VALUES[i] as usize] = Some(PATTERNS[i]);
+        i += 1;
+    }
+    table
+};
+
+/// WCAG relative luminance of an sRGB color, from `0.0` to `1.0`
+fn relative_luminance((r, g, b): (u8, u8, u8)) -> f32 {
+    let linear = |channel: u8| {
+        let channel = channel as f32 / 255.0;
+        if channel <= 0.040_45 {
+            return channel / 12.92;
+        }
+        return libm::powf((channel + 0.055) / 1.055, 2.4);
+    };
+    return (0.2126 * linear(r)) + (0.7152 * linear(g)) + (0.0722 * linear(b));
+}
+
+/// Iterator over every [`Pattern`], in declaration order.
+///
+/// Created by [`Pattern::all()`]. It can also be reversed, and reports its exact length
+#[derive(Debug, Clone)]
+pub struct PatternIter {
+    inner: core::slice::Iter<'static, Pattern>,
+}
+
+impl Iterator for PatternIter {
+    type Item = Pattern;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        return self.inner.next().copied();
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        return self.inner.size_hint();
+    }
+}
+
+impl DoubleEndedIterator for PatternIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        return self.inner.next_back().copied();
+    }
+}
+
+impl ExactSizeIterator for PatternIter {}
+
+impl core::iter::FusedIterator for PatternIter {}
+
 impl Pattern {
 
+    /// Number of defined patterns
+    pub const COUNT: usize = PATTERNS.len();
+
+    /// The first pattern in declaration order, with the lowest raw value ([`Pattern::Rainbow`])
+    pub const FIRST: Pattern = PATTERNS[0];
+
+    /// The last pattern in declaration order, with the highest raw value ([`Pattern::Black`])
+    pub const LAST: Pattern = PATTERNS[Pattern::COUNT - 1];
+
+    /// PWM period expected by the driver in microseconds (a 50Hz frame)
+    pub const FRAME_PERIOD_US: u16 = 20_000;
+
+    /// Length in bytes of the longest entry produced by [`Pattern::to_json_entry()`].
+    ///
+    /// Requires the `heapless` and `names` features
+    #[cfg(all(feature = "heapless", feature = "names"))]
+    pub const JSON_ENTRY_MAX_LEN: usize = {
+        let mut longest_name = 0;
+        let mut i = 0;
+        while i < Pattern::COUNT {
+            if PATTERNS[i].name().len() > longest_name {
+                longest_name = PATTERNS[i].name().len();
+            }
+            i += 1;
+        }
+        // `{"name":"","value":}` plus the name and up to three digits of raw value
+        longest_name + 20 + 3
+    };
+
+    /// Every pattern, in the same declaration order as [`Pattern::all()`]
+    pub const ALL: &'static [Pattern] = &PATTERNS;
+
+    /// The raw value of every pattern, in declaration order (which is also sorted)
+    pub const RAW_VALUES: [u8; Pattern::COUNT] = {
+        let mut values = [0; Pattern::COUNT];
+        let mut i = 0;
+        while i < Pattern::COUNT {
+            values[i] = PATTERNS[i].as_raw();
+            i += 1;
+        }
+        values
+    };
+
+    /// The datasheet entry of every pattern, in declaration order
+    pub const TABLE: [PatternInfo; Pattern::COUNT] = {
+        let mut table = [Pattern::Rainbow.info(); Pattern::COUNT];
+        let mut i = 0;
+        while i < Pattern::COUNT {
+            table[i] = PATTERNS[i].info();
+            i += 1;
+        }
+        table
+    };
+
+    /// Get the datasheet entry for this pattern
+    pub const fn info(&self) -> PatternInfo {
+        return PatternInfo {
+            pattern: *self,
+            #[cfg(feature = "names")]
+            name: self.name(),
+            #[cfg(feature = "names")]
+            display_name: self.display_name(),
+            raw: self.as_raw(),
+            pulse_width_us: self.as_pulse_width_us(),
+        };
+    }
+
+    /// Get the pattern that turns the LEDs off ([`Pattern::Black`])
+    pub const fn off() -> Pattern {
+        return Pattern::Black;
+    }
+
+    /// Get a sensible visible pattern for when the LEDs should simply be on ([`Pattern::Blue`])
+    pub const fn default_color() -> Pattern {
+        return Pattern::Blue;
+    }
+
+    /// Get the pattern to show at boot to confirm the LED wiring works ([`Pattern::Blue`]).
+    ///
+    /// A solid color is used so nothing moves or flashes, making a dead or miswired LED easy
+    /// to spot, and blue is clearly visible without being harsh. This is not the [`Default`]
+    /// pattern, which is [`Pattern::Black`] (off)
+    pub const fn safe_startup() -> Pattern {
+        return Pattern::Blue;
+    }
+
+    /// Iterate over every pattern in declaration order (which is also raw value order)
+    pub fn all() -> PatternIter {
+        return PatternIter {
+            inner: PATTERNS.iter(),
+        };
+    }
+
+    /// Get the Rust identifier of this pattern, eg. `"RainbowForest"`
+    ///
+    /// Requires the `names` feature (enabled by default)
+    #[cfg(feature = "names")]
+    pub const fn name(&self) -> &'static str {
+        return match self {
+            Pattern::Rainbow => "Rainbow",
+            Pattern::RainbowParty => "RainbowParty",
+            Pattern::RainbowOcean => "RainbowOcean",
+            Pattern::RainbowLava => "RainbowLava",
+            Pattern::RainbowForest => "RainbowForest",
+            Pattern::RainbowGlitter => "RainbowGlitter",
+            Pattern::Confetti => "Confetti",
+            Pattern::RedShot => "RedShot",
+            Pattern::BlueShot => "BlueShot",
+            Pattern::WhiteShot => "WhiteShot",
+            Pattern::SinelonRainbow => "SinelonRainbow",
+            Pattern::SinelonParty => "SinelonParty",
+            Pattern::SinelonOcean => "SinelonOcean",
+            Pattern::SinelonLava => "SinelonLava",
+            Pattern::SinelonForest => "SinelonForest",
+            Pattern::BpmRainbow => "BpmRainbow",
+            Pattern::BpmOcean => "BpmOcean",
+            Pattern::BpmLava => "BpmLava",
+            Pattern::BpmForest => "BpmForest",
+            Pattern::FireMedium => "FireMedium",
+            Pattern::FireLarge => "FireLarge",
+            Pattern::TwinklesRainbow => "TwinklesRainbow",
+            Pattern::TwinklesParty => "TwinklesParty",
+            Pattern::TwinklesOcean => "TwinklesOcean",
+            Pattern::TwinklesLava => "TwinklesLava",
+            Pattern::TwinklesForest => "TwinklesForest",
+            Pattern::WavesRainbow => "WavesRainbow",
+            Pattern::WavesParty => "WavesParty",
+            Pattern::WavesOcean => "WavesOcean",
+            Pattern::WavesLava => "WavesLava",
+            Pattern::WavesForest => "WavesForest",
+            Pattern::LarsonRed => "LarsonRed",
+            Pattern::LarsonGray => "LarsonGray",
+            Pattern::ChaseRed => "ChaseRed",
+            Pattern::ChaseBlue => "ChaseBlue",
+            Pattern::ChaseGray => "ChaseGray",
+            Pattern::HeartbeatRed => "HeartbeatRed",
+            Pattern::HeartbeatBlue => "HeartbeatBlue",
+            Pattern::HeartbeatWhite => "HeartbeatWhite",
+            Pattern::HeartbeatGray => "HeartbeatGray",
+            Pattern::BreathRed => "BreathRed",
+            Pattern::BreathBlue => "BreathBlue",
+            Pattern::BreathGray => "BreathGray",
+            Pattern::StrobeBlue => "StrobeBlue",
+            Pattern::StrobeGold => "StrobeGold",
+            Pattern::StrobeWhite => "StrobeWhite",
+            Pattern::Color1BlendToBlack => "Color1BlendToBlack",
+            Pattern::Color1Larson => "Color1Larson",
+            Pattern::Color1Chase => "Color1Chase",
+            Pattern::Color1HeartbeatSlow => "Color1HeartbeatSlow",
+            Pattern::Color1HeartbeatMedium => "Color1HeartbeatMedium",
+            Pattern::Color1HeartbeatFast => "Color1HeartbeatFast",
+            Pattern::Color1BreathSlow => "Color1BreathSlow",
+            Pattern::Color1BreathFast => "Color1BreathFast",
+            Pattern::Color1Shot => "Color1Shot",
+            Pattern::Color1Strobe => "Color1Strobe",
+            Pattern::Color2BlendToBlack => "Color2BlendToBlack",
+            Pattern::Color2Larson => "Color2Larson",
+            Pattern::Color2Chase => "Color2Chase",
+            Pattern::Color2HeartbeatSlow => "Color2HeartbeatSlow",
+            Pattern::Color2HeartbeatMedium => "Color2HeartbeatMedium",
+            Pattern::Color2HeartbeatFast => "Color2HeartbeatFast",
+            Pattern::Color2BreathSlow => "Color2BreathSlow",
+            Pattern::Color2BreathFast => "Color2BreathFast",
+            Pattern::Color2Shot => "Color2Shot",
+            Pattern::Color2Strobe => "Color2Strobe",
+            Pattern::Sparkle1On2 => "Sparkle1On2",
+            Pattern::Sparkle2On1 => "Sparkle2On1",
+            Pattern::Gradient1And2 => "Gradient1And2",
+            Pattern::Bpm1And2 => "Bpm1And2",
+            Pattern::EndBlend1And2 => "EndBlend1And2",
+            Pattern::EndBlend => "EndBlend",
+            Pattern::Color1And2NoBlend => "Color1And2NoBlend",
+            Pattern::Twinkle1And2 => "Twinkle1And2",
+            Pattern::Waves1And2 => "Waves1And2",
+            Pattern::Sinelon1And2 => "Sinelon1And2",
+            Pattern::HotPink => "HotPink",
+            Pattern::DarkRed => "DarkRed",
+            Pattern::Red => "Red",
+            Pattern::RedOrange => "RedOrange",
+            Pattern::Orange => "Orange",
+            Pattern::Gold => "Gold",
+            Pattern::Yellow => "Yellow",
+            Pattern::LawnGreen => "LawnGreen",
+            Pattern::Lime => "Lime",
+            Pattern::DarkGreen => "DarkGreen",
+            Pattern::Green => "Green",
+            Pattern::BlueGreen => "BlueGreen",
+            Pattern::Aqua => "Aqua",
+            Pattern::SkyBlue => "SkyBlue",
+            Pattern::DarkBlue => "DarkBlue",
+            Pattern::Blue => "Blue",
+            Pattern::BlueViolet => "BlueViolet",
+            Pattern::Violet => "Violet",
+            Pattern::White => "White",
+            Pattern::Gray => "Gray",
+            Pattern::DarkGray => "DarkGray",
+            Pattern::Black => "Black",
+        };
+    }
+
+    /// Get the pattern's name as it appears in the user manual's color table, eg. `"Rainbow, Forest Palette"`.
+    ///
+    /// Patterns listed under the manual's "Color 1" and "Color 2" headings are
+    /// prefixed with their heading, eg. `"Color 1, Light Chase"`. Requires the `names` feature
+    #[cfg(feature = "names")]
+    pub const fn display_name(&self) -> &'static str {
+        return match self {
+            Pattern::Rainbow => "Rainbow, Rainbow Palette",
+            Pattern::RainbowParty => "Rainbow, Party Palette",
+            Pattern::RainbowOcean => "Rainbow, Ocean Palette",
+            Pattern::RainbowLava => "Rainbow, Lava Palette",
+            Pattern::RainbowForest => "Rainbow, Forest Palette",
+            Pattern::RainbowGlitter => "Rainbow with Glitter",
+            Pattern::Confetti => "Confetti",
+            Pattern::RedShot => "Shot, Red",
+            Pattern::BlueShot => "Shot, Blue",
+            Pattern::WhiteShot => "Shot, White",
+            Pattern::SinelonRainbow => "Sinelon, Rainbow Palette",
+            Pattern::SinelonParty => "Sinelon, Party Palette",
+            Pattern::SinelonOcean => "Sinelon, Ocean Palette",
+            Pattern::SinelonLava => "Sinelon, Lava Palette",
+            Pattern::SinelonForest => "Sinelon, Forest Palette",
+            Pattern::BpmRainbow => "Beats per Minute, Rainbow Palette",
+            Pattern::BpmOcean => "Beats per Minute, Ocean Palette",
+            Pattern::BpmLava => "Beats per Minute, Lava Palette",
+            Pattern::BpmForest => "Beats per Minute, Forest Palette",
+            Pattern::FireMedium => "Fire, Medium",
+            Pattern::FireLarge => "Fire, Large",
+            Pattern::TwinklesRainbow => "Twinkles, Rainbow Palette",
+            Pattern::TwinklesParty => "Twinkles, Party Palette",
+            Pattern::TwinklesOcean => "Twinkles, Ocean Palette",
+            Pattern::TwinklesLava => "Twinkles, Lava Palette",
+            Pattern::TwinklesForest => "Twinkles, Forest Palette",
+            Pattern::WavesRainbow => "Color Waves, Rainbow Palette",
+            Pattern::WavesParty => "Color Waves, Party Palette",
+            Pattern::WavesOcean => "Color Waves, Ocean Palette",
+            Pattern::WavesLava => "Color Waves, Lava Palette",
+            Pattern::WavesForest => "Color Waves, Forest Palette",
+            Pattern::LarsonRed => "Larson Scanner, Red",
+            Pattern::LarsonGray => "Larson Scanner, Gray",
+            Pattern::ChaseRed => "Light Chase, Red",
+            Pattern::ChaseBlue => "Light Chase, Blue",
+            Pattern::ChaseGray => "Light Chase, Gray",
+            Pattern::HeartbeatRed => "Heartbeat, Red",
+            Pattern::HeartbeatBlue => "Heartbeat, Blue",
+            Pattern::HeartbeatWhite => "Heartbeat, White",
+            Pattern::HeartbeatGray => "Heartbeat, Gray",
+            Pattern::BreathRed => "Breath, Red",
+            Pattern::BreathBlue => "Breath, Blue",
+            Pattern::BreathGray => "Breath, Gray",
+            Pattern::StrobeBlue => "Strobe, Blue",
+            Pattern::StrobeGold => "Strobe, Gold",
+            Pattern::StrobeWhite => "Strobe, White",
+            Pattern::Color1BlendToBlack => "Color 1, End to End Blend to Black",
+            Pattern::Color1Larson => "Color 1, Larson Scanner",
+            Pattern::Color1Chase => "Color 1, Light Chase",
+            Pattern::Color1HeartbeatSlow => "Color 1, Heartbeat Slow",
+            Pattern::Color1HeartbeatMedium => "Color 1, Heartbeat Medium",
+            Pattern::Color1HeartbeatFast => "Color 1, Heartbeat Fast",
+            Pattern::Color1BreathSlow => "Color 1, Breath Slow",
+            Pattern::Color1BreathFast => "Color 1, Breath Fast",
+            Pattern::Color1Shot => "Color 1, Shot",
+            Pattern::Color1Strobe => "Color 1, Strobe",
+            Pattern::Color2BlendToBlack => "Color 2, End to End Blend to Black",
+            Pattern::Color2Larson => "Color 2, Larson Scanner",
+            Pattern::Color2Chase => "Color 2, Light Chase",
+            Pattern::Color2HeartbeatSlow => "Color 2, Heartbeat Slow",
+            Pattern::Color2HeartbeatMedium => "Color 2, Heartbeat Medium",
+            Pattern::Color2HeartbeatFast => "Color 2, Heartbeat Fast",
+            Pattern::Color2BreathSlow => "Color 2, Breath Slow",
+            Pattern::Color2BreathFast => "Color 2, Breath Fast",
+            Pattern::Color2Shot => "Color 2, Shot",
+            Pattern::Color2Strobe => "Color 2, Strobe",
+            Pattern::Sparkle1On2 => "Sparkle, Color 1 on Color 2",
+            Pattern::Sparkle2On1 => "Sparkle, Color 2 on Color 1",
+            Pattern::Gradient1And2 => "Color Gradient, Color 1 and 2",
+            Pattern::Bpm1And2 => "Beats per Minute, Color 1 and 2",
+            Pattern::EndBlend1And2 => "End to End Blend, Color 1 to 2",
+            Pattern::EndBlend => "End to End Blend",
+            Pattern::Color1And2NoBlend => "Color 1 and Color 2 no blending",
+            Pattern::Twinkle1And2 => "Twinkles, Color 1 and 2",
+            Pattern::Waves1And2 => "Color Waves, Color 1 and 2",
+            Pattern::Sinelon1And2 => "Sinelon, Color 1 and 2",
+            Pattern::HotPink => "Hot Pink",
+            Pattern::DarkRed => "Dark Red",
+            Pattern::Red => "Red",
+            Pattern::RedOrange => "Red Orange",
+            Pattern::Orange => "Orange",
+            Pattern::Gold => "Gold",
+            Pattern::Yellow => "Yellow",
+            Pattern::LawnGreen => "Lawn Green",
+            Pattern::Lime => "Lime",
+            Pattern::DarkGreen => "Dark Green",
+            Pattern::Green => "Green",
+            Pattern::BlueGreen => "Blue Green",
+            Pattern::Aqua => "Aqua",
+            Pattern::SkyBlue => "Sky Blue",
+            Pattern::DarkBlue => "Dark Blue",
+            Pattern::Blue => "Blue",
+            Pattern::BlueViolet => "Blue Violet",
+            Pattern::Violet => "Violet",
+            Pattern::White => "White",
+            Pattern::Gray => "Gray",
+            Pattern::DarkGray => "Dark Gray",
+            Pattern::Black => "Black",
+        };
+    }
+
+    /// Get a short description of what this pattern looks like, for tooltips and help text.
+    ///
+    /// This is a terse summary of the user manual's pattern notes, and is unrelated to
+    /// [`Pattern::display_name()`]. Requires the `names` feature
+    #[cfg(feature = "names")]
+    pub const fn describe(&self) -> &'static str {
+        return match self {
+            Pattern::Rainbow => "Rainbow palette scrolling along the strip",
+            Pattern::RainbowParty => "Party palette scrolling along the strip",
+            Pattern::RainbowOcean => "Ocean palette scrolling along the strip",
+            Pattern::RainbowLava => "Lava palette scrolling along the strip",
+            Pattern::RainbowForest => "Forest palette scrolling along the strip",
+            Pattern::RainbowGlitter => "Scrolling rainbow with random white glitter",
+            Pattern::Confetti => "Random colored sparkles that fade out",
+            Pattern::RedShot => "Single red pulse fired down the strip once",
+            Pattern::BlueShot => "Single blue pulse fired down the strip once",
+            Pattern::WhiteShot => "Single white pulse fired down the strip once",
+            Pattern::SinelonRainbow => "Rainbow palette dot sweeping back and forth with a fading trail",
+            Pattern::SinelonParty => "Party palette dot sweeping back and forth with a fading trail",
+            Pattern::SinelonOcean => "Ocean palette dot sweeping back and forth with a fading trail",
+            Pattern::SinelonLava => "Lava palette dot sweeping back and forth with a fading trail",
+            Pattern::SinelonForest => "Forest palette dot sweeping back and forth with a fading trail",
+            Pattern::BpmRainbow => "Rainbow palette pulsing to a beat",
+            Pattern::BpmOcean => "Ocean palette pulsing to a beat",
+            Pattern::BpmLava => "Lava palette pulsing to a beat",
+            Pattern::BpmForest => "Forest palette pulsing to a beat",
+            Pattern::FireMedium => "Flickering fire simulation, medium flames",
+            Pattern::FireLarge => "Flickering fire simulation, large flames",
+            Pattern::TwinklesRainbow => "Rainbow palette twinkling in and out",
+            Pattern::TwinklesParty => "Party palette twinkling in and out",
+            Pattern::TwinklesOcean => "Ocean palette twinkling in and out",
+            Pattern::TwinklesLava => "Lava palette twinkling in and out",
+            Pattern::TwinklesForest => "Forest palette twinkling in and out",
+            Pattern::WavesRainbow => "Rainbow palette rolling in waves",
+            Pattern::WavesParty => "Party palette rolling in waves",
+            Pattern::WavesOcean => "Ocean palette rolling in waves",
+            Pattern::WavesLava => "Lava palette rolling in waves",
+            Pattern::WavesForest => "Forest palette rolling in waves",
+            Pattern::LarsonRed => "Red single-LED bounce (Larson scanner)",
+            Pattern::LarsonGray => "Gray single-LED bounce (Larson scanner)",
+            Pattern::ChaseRed => "Red light chase running along the strip",
+            Pattern::ChaseBlue => "Blue light chase running along the strip",
+            Pattern::ChaseGray => "Gray light chase running along the strip",
+            Pattern::HeartbeatRed => "Red double pulse like a heartbeat",
+            Pattern::HeartbeatBlue => "Blue double pulse like a heartbeat",
+            Pattern::HeartbeatWhite => "White double pulse like a heartbeat",
+            Pattern::HeartbeatGray => "Gray double pulse like a heartbeat",
+            Pattern::BreathRed => "Red slowly fading in and out",
+            Pattern::BreathBlue => "Blue slowly fading in and out",
+            Pattern::BreathGray => "Gray slowly fading in and out",
+            Pattern::StrobeBlue => "Blue rapid flashing strobe",
+            Pattern::StrobeGold => "Gold rapid flashing strobe",
+            Pattern::StrobeWhite => "White rapid flashing strobe",
+            Pattern::Color1BlendToBlack => "Color 1 slowly fading to black and back",
+            Pattern::Color1Larson => "Color 1 single-LED bounce (Larson scanner)",
+            Pattern::Color1Chase => "Color 1 light chase running along the strip",
+            Pattern::Color1HeartbeatSlow => "Color 1 slow heartbeat double pulse",
+            Pattern::Color1HeartbeatMedium => "Color 1 medium heartbeat double pulse",
+            Pattern::Color1HeartbeatFast => "Color 1 fast heartbeat double pulse",
+            Pattern::Color1BreathSlow => "Color 1 slowly fading in and out",
+            Pattern::Color1BreathFast => "Color 1 quickly fading in and out",
+            Pattern::Color1Shot => "Single Color 1 pulse fired down the strip once",
+            Pattern::Color1Strobe => "Color 1 rapid flashing strobe",
+            Pattern::Color2BlendToBlack => "Color 2 slowly fading to black and back",
+            Pattern::Color2Larson => "Color 2 single-LED bounce (Larson scanner)",
+            Pattern::Color2Chase => "Color 2 light chase running along the strip",
+            Pattern::Color2HeartbeatSlow => "Color 2 slow heartbeat double pulse",
+            Pattern::Color2HeartbeatMedium => "Color 2 medium heartbeat double pulse",
+            Pattern::Color2HeartbeatFast => "Color 2 fast heartbeat double pulse",
+            Pattern::Color2BreathSlow => "Color 2 slowly fading in and out",
+            Pattern::Color2BreathFast => "Color 2 quickly fading in and out",
+            Pattern::Color2Shot => "Single Color 2 pulse fired down the strip once",
+            Pattern::Color2Strobe => "Color 2 rapid flashing strobe",
+            Pattern::Sparkle1On2 => "Color 1 sparkles over a Color 2 background",
+            Pattern::Sparkle2On1 => "Color 2 sparkles over a Color 1 background",
+            Pattern::Gradient1And2 => "Static gradient from Color 1 to Color 2",
+            Pattern::Bpm1And2 => "Color 1 and 2 pulsing to a beat",
+            Pattern::EndBlend1And2 => "Color 1 at one end blending to Color 2 at the other",
+            Pattern::EndBlend => "Color 1 and 2 blended from each end of the strip",
+            Pattern::Color1And2NoBlend => "Alternating Color 1 and 2 segments, no blending",
+            Pattern::Twinkle1And2 => "Color 1 and 2 twinkling in and out",
+            Pattern::Waves1And2 => "Color 1 and 2 rolling in waves",
+            Pattern::Sinelon1And2 => "Color 1 and 2 dots sweeping back and forth",
+            Pattern::HotPink => "Solid hot pink",
+            Pattern::DarkRed => "Solid dark red",
+            Pattern::Red => "Solid red",
+            Pattern::RedOrange => "Solid red orange",
+            Pattern::Orange => "Solid orange",
+            Pattern::Gold => "Solid gold",
+            Pattern::Yellow => "Solid yellow",
+            Pattern::LawnGreen => "Solid lawn green",
+            Pattern::Lime => "Solid lime",
+            Pattern::DarkGreen => "Solid dark green",
+            Pattern::Green => "Solid green",
+            Pattern::BlueGreen => "Solid blue green",
+            Pattern::Aqua => "Solid aqua",
+            Pattern::SkyBlue => "Solid sky blue",
+            Pattern::DarkBlue => "Solid dark blue",
+            Pattern::Blue => "Solid blue",
+            Pattern::BlueViolet => "Solid blue violet",
+            Pattern::Violet => "Solid violet",
+            Pattern::White => "Solid white",
+            Pattern::Gray => "Solid gray",
+            Pattern::DarkGray => "Solid dark gray",
+            Pattern::Black => "All LEDs off",
+        };
+    }
+
+    /// Find the pattern whose variant name matches `s`, ignoring case and any characters
+    /// that are not ASCII letters or digits.
+    ///
+    /// So `"rainbow forest"`, `"RAINBOW_FOREST"` and `"rainbowforest"` all resolve to
+    /// [`Pattern::RainbowForest`]. This compares the bytes in place, without any buffer.
+    /// Requires the `names` feature
+    #[cfg(feature = "names")]
+    pub fn from_name_loose(s: &str) -> Option<Pattern> {
+        let normalized = || {
+            s.bytes()
+                .filter(u8::is_ascii_alphanumeric)
+                .map(|byte| byte.to_ascii_lowercase())
+        };
+        return Pattern::all().find(|pattern| {
+            normalized().eq(pattern.name().bytes().map(|byte| byte.to_ascii_lowercase()))
+        });
+    }
+
+    /// Check if `value` is the raw value of a defined pattern.
+    ///
+    /// Defined patterns only use odd values from `1..=199`. Even values, and anything
+    /// from `200` up, are never valid. Values `33` and `89` are also undefined here: the
+    /// datasheet lists them (as "Beats per Minute, Party Palette" and "Strobe, Red"), but
+    /// this crate does not transcribe them as patterns
+    pub const fn is_valid_raw(value: u8) -> bool {
+        return DECODE_TABLE[value as usize].is_some();
+    }
+
+    /// Get the raw datasheet value of this pattern, from `0..200`
+    pub const fn as_raw(&self) -> u8 {
+        return *self as u8;
+    }
+
+    /// Decode a raw datasheet value, or `None` if no pattern uses it
+    ///
+    /// This is the `const` counterpart to `Pattern::try_from(u8)`, usable when building
+    /// static configuration tables
+    pub const fn from_raw(value: u8) -> Option<Pattern> {
+        return DECODE_TABLE[value as usize];
+    }
+
+    /// Parse a raw datasheet value written in decimal, eg. `"161"` for [`Pattern::Red`].
+    ///
+    /// Returns `None` if `s` is not a decimal `u8`, or if no pattern uses that value
+    pub fn from_numeric_str(s: &str) -> Option<Pattern> {
+        return Pattern::from_raw(s.parse::<u8>().ok()?);
+    }
+
+    /// Decode a raw datasheet value, falling back to the closest defined pattern.
+    ///
+    /// Values in a gap resolve to the nearest pattern by raw distance, breaking ties towards
+    /// the higher pattern (so even values round up). `0` resolves to `Rainbow`, and anything
+    /// above `199` to `Black`
+    pub fn from_raw_clamped(value: u8) -> Pattern {
+        return Pattern::from_raw(value).unwrap_or_else(|| Pattern::nearest_to_raw(value as f32));
+    }
+
+    /// Endlessly iterate over every pattern in declaration order, starting at `start`
+    /// and wrapping from `Black` back to `Rainbow`
+    pub fn cycle_from(start: Pattern) -> impl Iterator<Item = Pattern> {
+        return PATTERNS.iter().copied().cycle().skip(start.index());
+    }
+
+    /// Iterate over every pattern with its pulse width in microseconds, from shortest to longest.
+    ///
+    /// Pulse width order is the same as declaration order, so this is intended for tooling
+    /// that sweeps the driver through its whole range
+    pub fn sweep() -> impl Iterator<Item = (Pattern, u16)> {
+        return Pattern::all().map(|pattern| (pattern, pattern.as_pulse_width_us()));
+    }
+
+    /// Iterate over the fixed solid-color patterns, in declaration order
+    pub fn solids() -> impl Iterator<Item = Pattern> {
+        return Pattern::in_category(Category::SolidColor);
+    }
+
+    /// Iterate over the chromatic solid colors in order of ascending hue, starting from red,
+    /// for building a color wheel.
+    ///
+    /// Hues come from [`Pattern::hsv()`], and colors with equal hues stay in declaration order.
+    /// Colors without saturation (`White`, `Gray`, `DarkGray` and `Black`) are skipped
+    pub fn hue_ring() -> impl Iterator<Item = Pattern> {
+        let mut ring = [Pattern::Black; Pattern::COUNT];
+        let mut len = 0;
+        for pattern in Pattern::solids() {
+            if matches!(pattern.hsv(), Some((_, saturation, _)) if saturation > 0.0) {
+                ring[len] = pattern;
+                len += 1;
+            }
+        }
+
+        let hue = |pattern: &Pattern| pattern.hsv().unwrap().0;
+        ring[..len].sort_unstable_by(|a, b| hue(a).partial_cmp(&hue(b)).unwrap().then(a.cmp(b)));
+        return IntoIterator::into_iter(ring).take(len);
+    }
+
+    /// Iterate over every pattern in `category`, in declaration order
+    pub fn in_category(category: Category) -> impl Iterator<Item = Pattern> {
+        return Pattern::all().filter(move |pattern| pattern.category() == category);
+    }
+
+    /// Pick a pattern uniformly at random from every defined pattern.
+    ///
+    /// Requires the `rand` feature
+    #[cfg(feature = "rand")]
+    pub fn random<R: rand_core::RngCore>(rng: &mut R) -> Pattern {
+        return PATTERNS[random_index(rng, PATTERNS.len())];
+    }
+
+    /// Pick a pattern uniformly at random from the patterns in `category`.
+    ///
+    /// Requires the `rand` feature
+    #[cfg(feature = "rand")]
+    pub fn random_in<R: rand_core::RngCore>(category: Category, rng: &mut R) -> Pattern {
+        let count = Pattern::in_category(category).count();
+        return Pattern::in_category(category)
+            .nth(random_index(rng, count))
+            .unwrap();
+    }
+
+    /// Get the position of this pattern in declaration order, from `0..Pattern::COUNT`.
+    ///
+    /// Unlike the raw value this is dense, so `Pattern::ALL[pattern.index()] == pattern`
+    pub fn index(&self) -> usize {
+        return Pattern::RAW_VALUES.binary_search(&self.as_raw()).unwrap();
+    }
+
+    /// Get the pattern at `index` in declaration order, or `None` if `index` is not below `Pattern::COUNT`
+    pub fn from_index(index: usize) -> Option<Pattern> {
+        return PATTERNS.get(index).copied();
+    }
+
+    /// Get the next pattern in declaration order, wrapping from `Black` back to `Rainbow`
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&self) -> Pattern {
+        return PATTERNS[(self.index() + 1) % PATTERNS.len()];
+    }
+
+    /// Get the previous pattern in declaration order, wrapping from `Rainbow` back to `Black`
+    pub fn prev(&self) -> Pattern {
+        return PATTERNS[(self.index() + PATTERNS.len() - 1) % PATTERNS.len()];
+    }
+
+    /// Move `n` patterns forward in declaration order (or backward if `n` is negative),
+    /// wrapping around at either end.
+    ///
+    /// This is equivalent to calling [`Pattern::next()`] or [`Pattern::prev()`] `|n|` times
+    pub fn step_by(&self, n: i32) -> Pattern {
+        let count = Pattern::COUNT as i64;
+        let position = (self.index() as i64 + n as i64).rem_euclid(count);
+        return PATTERNS[position as usize];
+    }
+
+    /// Interpolate between this pattern's and `other`'s raw values by `t`, and snap to the
+    /// nearest defined pattern.
+    ///
+    /// `t` is clamped to `0.0..=1.0`, where `0.0` gives `self` and `1.0` gives `other`. This
+    /// walks the datasheet table in order, so the intermediate patterns only make visual sense
+    /// within a single family (eg. between two solid colors)
+    pub fn blend_toward(&self, other: Pattern, t: f32) -> Pattern {
+        let t = if t > 0.0 { t.min(1.0) } else { 0.0 };
+        let from = self.as_raw() as f32;
+        let raw = from + ((other.as_raw() as f32 - from) * t);
+        return Pattern::nearest_to_raw(raw);
+    }
+
+    /// Get the next pattern in declaration order, or `None` if this is [`Pattern::LAST`]
+    pub fn checked_next(&self) -> Option<Pattern> {
+        return PATTERNS.get(self.index() + 1).copied();
+    }
+
+    /// Get the previous pattern in declaration order, or `None` if this is [`Pattern::FIRST`]
+    pub fn checked_prev(&self) -> Option<Pattern> {
+        return self.index().checked_sub(1).map(|position| PATTERNS[position]);
+    }
+
+    /// Check if this is the first pattern of its [`Category`], in declaration order.
+    ///
+    /// See [`Category::first_pattern()`]
+    pub fn is_category_first(&self) -> bool {
+        return self.category().first_pattern() == *self;
+    }
+
+    /// Get the next pattern in the same [`Category`], wrapping from the last member back to the first
+    pub fn next_in_category(&self) -> Pattern {
+        let category = self.category();
+        return Pattern::in_category(category)
+            .find(|pattern| pattern > self)
+            .unwrap_or_else(|| Pattern::in_category(category).next().unwrap());
+    }
+
+    /// Get the previous pattern in the same [`Category`], wrapping from the first member back to the last
+    pub fn prev_in_category(&self) -> Pattern {
+        let category = self.category();
+        return Pattern::in_category(category)
+            .filter(|pattern| pattern < self)
+            .last()
+            .unwrap_or_else(|| Pattern::in_category(category).last().unwrap());
+    }
+
+    /// Get the family this pattern belongs to
+    pub fn category(&self) -> Category {
+        return match self {
+            Pattern::Rainbow | Pattern::RainbowParty | Pattern::RainbowOcean
+            | Pattern::RainbowLava | Pattern::RainbowForest | Pattern::RainbowGlitter => Category::Rainbow,
+            Pattern::Confetti => Category::Confetti,
+            Pattern::RedShot | Pattern::BlueShot | Pattern::WhiteShot => Category::Shot,
+            Pattern::SinelonRainbow | Pattern::SinelonParty | Pattern::SinelonOcean
+            | Pattern::SinelonLava | Pattern::SinelonForest => Category::Sinelon,
+            Pattern::BpmRainbow | Pattern::BpmOcean | Pattern::BpmLava | Pattern::BpmForest => Category::Bpm,
+            Pattern::FireMedium | Pattern::FireLarge => Category::Fire,
+            Pattern::TwinklesRainbow | Pattern::TwinklesParty | Pattern::TwinklesOcean
+            | Pattern::TwinklesLava | Pattern::TwinklesForest => Category::Twinkles,
+            Pattern::WavesRainbow | Pattern::WavesParty | Pattern::WavesOcean
+            | Pattern::WavesLava | Pattern::WavesForest => Category::Waves,
+            Pattern::LarsonRed | Pattern::LarsonGray => Category::Larson,
+            Pattern::ChaseRed | Pattern::ChaseBlue | Pattern::ChaseGray => Category::Chase,
+            Pattern::HeartbeatRed | Pattern::HeartbeatBlue | Pattern::HeartbeatWhite
+            | Pattern::HeartbeatGray => Category::Heartbeat,
+            Pattern::BreathRed | Pattern::BreathBlue | Pattern::BreathGray => Category::Breath,
+            Pattern::StrobeBlue | Pattern::StrobeGold | Pattern::StrobeWhite => Category::Strobe,
+            Pattern::Color1BlendToBlack | Pattern::Color1Larson | Pattern::Color1Chase
+            | Pattern::Color1HeartbeatSlow | Pattern::Color1HeartbeatMedium
+            | Pattern::Color1HeartbeatFast | Pattern::Color1BreathSlow
+            | Pattern::Color1BreathFast | Pattern::Color1Shot | Pattern::Color1Strobe => Category::Color1,
+            Pattern::Color2BlendToBlack | Pattern::Color2Larson | Pattern::Color2Chase
+            | Pattern::Color2HeartbeatSlow | Pattern::Color2HeartbeatMedium
+            | Pattern::Color2HeartbeatFast | Pattern::Color2BreathSlow
+            | Pattern::Color2BreathFast | Pattern::Color2Shot | Pattern::Color2Strobe => Category::Color2,
+            Pattern::Sparkle1On2 | Pattern::Sparkle2On1 | Pattern::Gradient1And2
+            | Pattern::Bpm1And2 | Pattern::EndBlend1And2 | Pattern::EndBlend
+            | Pattern::Color1And2NoBlend | Pattern::Twinkle1And2 | Pattern::Waves1And2
+            | Pattern::Sinelon1And2 => Category::Color1And2,
+            Pattern::HotPink | Pattern::DarkRed | Pattern::Red | Pattern::RedOrange
+            | Pattern::Orange | Pattern::Gold | Pattern::Yellow | Pattern::LawnGreen
+            | Pattern::Lime | Pattern::DarkGreen | Pattern::Green | Pattern::BlueGreen
+            | Pattern::Aqua | Pattern::SkyBlue | Pattern::DarkBlue | Pattern::Blue
+            | Pattern::BlueViolet | Pattern::Violet | Pattern::White | Pattern::Gray
+            | Pattern::DarkGray | Pattern::Black => Category::SolidColor,
+        };
+    }
+
+    /// Get the motion style of this pattern
+    pub fn effect(&self) -> Effect {
+        return match self {
+            Pattern::Rainbow | Pattern::RainbowParty | Pattern::RainbowOcean
+            | Pattern::RainbowLava | Pattern::RainbowForest => Effect::Rainbow,
+            Pattern::RainbowGlitter => Effect::Glitter,
+            Pattern::Confetti => Effect::Confetti,
+            Pattern::RedShot | Pattern::BlueShot | Pattern::WhiteShot | Pattern::Color1Shot
+            | Pattern::Color2Shot => Effect::Shot,
+            Pattern::SinelonRainbow | Pattern::SinelonParty | Pattern::SinelonOcean
+            | Pattern::SinelonLava | Pattern::SinelonForest | Pattern::Sinelon1And2 => Effect::Sinelon,
+            Pattern::BpmRainbow | Pattern::BpmOcean | Pattern::BpmLava | Pattern::BpmForest
+            | Pattern::Bpm1And2 => Effect::Bpm,
+            Pattern::FireMedium | Pattern::FireLarge => Effect::Fire,
+            Pattern::TwinklesRainbow | Pattern::TwinklesParty | Pattern::TwinklesOcean
+            | Pattern::TwinklesLava | Pattern::TwinklesForest | Pattern::Twinkle1And2 => Effect::Twinkles,
+            Pattern::WavesRainbow | Pattern::WavesParty | Pattern::WavesOcean
+            | Pattern::WavesLava | Pattern::WavesForest | Pattern::Waves1And2 => Effect::Waves,
+            Pattern::LarsonRed | Pattern::LarsonGray | Pattern::Color1Larson
+            | Pattern::Color2Larson => Effect::Larson,
+            Pattern::ChaseRed | Pattern::ChaseBlue | Pattern::ChaseGray
+            | Pattern::Color1Chase | Pattern::Color2Chase => Effect::Chase,
+            Pattern::HeartbeatRed | Pattern::HeartbeatBlue | Pattern::HeartbeatWhite
+            | Pattern::HeartbeatGray | Pattern::Color1HeartbeatSlow
+            | Pattern::Color1HeartbeatMedium | Pattern::Color1HeartbeatFast
+            | Pattern::Color2HeartbeatSlow | Pattern::Color2HeartbeatMedium
+            | Pattern::Color2HeartbeatFast => Effect::Heartbeat,
+            Pattern::BreathRed | Pattern::BreathBlue | Pattern::BreathGray
+            | Pattern::Color1BreathSlow | Pattern::Color1BreathFast
+            | Pattern::Color2BreathSlow | Pattern::Color2BreathFast => Effect::Breath,
+            Pattern::StrobeBlue | Pattern::StrobeGold | Pattern::StrobeWhite
+            | Pattern::Color1Strobe | Pattern::Color2Strobe => Effect::Strobe,
+            Pattern::Color1BlendToBlack | Pattern::Color2BlendToBlack
+            | Pattern::EndBlend1And2 | Pattern::EndBlend => Effect::Blend,
+            Pattern::Sparkle1On2 | Pattern::Sparkle2On1 => Effect::Sparkle,
+            Pattern::Gradient1And2 => Effect::Gradient,
+            _ => Effect::Solid,
+        };
+    }
+
+    /// Get the named palette this pattern uses, if any
+    pub fn palette(&self) -> Palette {
+        return match self {
+            Pattern::Rainbow | Pattern::SinelonRainbow | Pattern::BpmRainbow
+            | Pattern::TwinklesRainbow | Pattern::WavesRainbow => Palette::Rainbow,
+            Pattern::RainbowParty | Pattern::SinelonParty | Pattern::TwinklesParty
+            | Pattern::WavesParty => Palette::Party,
+            Pattern::RainbowOcean | Pattern::SinelonOcean | Pattern::BpmOcean
+            | Pattern::TwinklesOcean | Pattern::WavesOcean => Palette::Ocean,
+            Pattern::RainbowLava | Pattern::SinelonLava | Pattern::BpmLava
+            | Pattern::TwinklesLava | Pattern::WavesLava => Palette::Lava,
+            Pattern::RainbowForest | Pattern::SinelonForest | Pattern::BpmForest
+            | Pattern::TwinklesForest | Pattern::WavesForest => Palette::Forest,
+            _ => Palette::None,
+        };
+    }
+
+    /// Find the pattern with `effect` that uses `palette`, if that combination exists.
+    ///
+    /// For example, [`Effect::Sinelon`] with [`Palette::Ocean`] resolves to [`Pattern::SinelonOcean`].
+    /// When several patterns match, the first in declaration order is returned
+    pub fn with_effect_and_palette(effect: Effect, palette: Palette) -> Option<Pattern> {
+        return Pattern::all()
+            .find(|pattern| pattern.effect() == effect && pattern.palette() == palette);
+    }
+
+    /// Find the pattern with the same effect as this one, but using `palette` instead.
+    ///
+    /// For example, [`Pattern::SinelonOcean`] with [`Palette::Forest`] resolves to
+    /// [`Pattern::SinelonForest`]. Returns `None` if the datasheet has no such combination
+    /// (there is no `BpmParty`). Asking for this pattern's own palette always returns itself
+    pub fn similar_in_palette(&self, palette: Palette) -> Option<Pattern> {
+        if self.palette() == palette {
+            return Some(*self);
+        }
+        return Pattern::with_effect_and_palette(self.effect(), palette);
+    }
+
+    /// Check if this pattern is one of the fixed solid colors (`HotPink` through `Black`)
+    pub fn is_solid(&self) -> bool {
+        return *self >= Pattern::HotPink;
+    }
+
+    /// Check if this pattern is animated (ie. not a fixed solid color)
+    pub fn is_animated(&self) -> bool {
+        return !self.is_solid();
+    }
+
+    /// Get a rough cycle length in milliseconds for the periodic heartbeat, breath, strobe
+    /// and sinelon patterns, or `None` for solid colors and aperiodic effects like `Confetti`.
+    ///
+    /// The datasheet only describes speeds relatively ("slow", "fast"), so these are estimates
+    /// chosen to keep `Slow`, `Medium` and `Fast` variants in order. They are good enough to
+    /// drive a preview animation, but not to synchronize with the driver
+    pub fn animation_period_ms(&self) -> Option<u32> {
+        return match self {
+            Pattern::Color1HeartbeatSlow | Pattern::Color2HeartbeatSlow => Some(1500),
+            Pattern::Color1HeartbeatFast | Pattern::Color2HeartbeatFast => Some(500),
+            Pattern::Color1BreathSlow | Pattern::Color2BreathSlow => Some(4000),
+            Pattern::Color1BreathFast | Pattern::Color2BreathFast => Some(2000),
+            _ => match self.effect() {
+                Effect::Heartbeat => Some(1000),
+                Effect::Breath => Some(3000),
+                Effect::Strobe => Some(100),
+                Effect::Sinelon => Some(2000),
+                _ => None,
+            },
+        };
+    }
+
+    /// Check if a preview of this pattern can be drawn as a still image.
+    ///
+    /// This is a UI heuristic, not a statement about the driver: solid colors and the static
+    /// two-color layouts (`Gradient1And2`, `Color1And2NoBlend`) never change, and the slow
+    /// breath patterns fade so gradually that a still preview is close enough. Everything
+    /// else moves quickly enough that a preview should keep animating
+    pub fn is_static_preview(&self) -> bool {
+        return self.is_solid()
+            || matches!(
+                self,
+                Pattern::Gradient1And2
+                    | Pattern::Color1And2NoBlend
+                    | Pattern::BreathRed
+                    | Pattern::BreathBlue
+                    | Pattern::BreathGray
+                    | Pattern::Color1BreathSlow
+                    | Pattern::Color2BreathSlow
+            );
+    }
+
+    /// Check if this pattern leaves the LEDs dark.
+    ///
+    /// `Black` is the only pattern the datasheet lists as fully off
+    pub fn is_off(&self) -> bool {
+        return matches!(self, Pattern::Black);
+    }
+
+    /// Check if this pattern plays once instead of looping.
+    ///
+    /// Shot patterns fire a single pulse down the strip and then stay dark. The Blinkin
+    /// only restarts a shot when it sees the PWM value change, so to fire again switch to
+    /// another pattern for at least one frame (see `Pattern::FRAME_PERIOD_US`) and then
+    /// send the shot again
+    pub fn is_one_shot(&self) -> bool {
+        return self.effect() == Effect::Shot;
+    }
+
+    /// Check if this pattern flashes rapidly.
+    ///
+    /// Use this to filter strobes out of pattern lists when photosensitivity is a concern
+    pub fn is_strobe(&self) -> bool {
+        return matches!(
+            self,
+            Pattern::StrobeBlue
+                | Pattern::StrobeGold
+                | Pattern::StrobeWhite
+                | Pattern::Color1Strobe
+                | Pattern::Color2Strobe
+        );
+    }
+
+    /// Check if this pattern depends on the user-configured Color 1
+    pub fn uses_color1(&self) -> bool {
+        return matches!(self.category(), Category::Color1 | Category::Color1And2);
+    }
+
+    /// Check if this pattern depends on the user-configured Color 2
+    pub fn uses_color2(&self) -> bool {
+        return matches!(self.category(), Category::Color2 | Category::Color1And2);
+    }
+
+    /// Check if this pattern combines both user-configured colors.
+    ///
+    /// This is every pattern in the manual's "Color 1 and 2" section, including the plain
+    /// `EndBlend` which blends between the two colors
+    pub fn is_two_color(&self) -> bool {
+        return self.category() == Category::Color1And2;
+    }
+
+    /// Get the user-configured color slots this pattern depends on.
+    ///
+    /// `Color1*` patterns need [`ColorSlot::Color1`], `Color2*` patterns need [`ColorSlot::Color2`],
+    /// and the Color 1 and 2 patterns (eg. `Gradient1And2`) need both
+    pub fn required_color_slots(&self) -> &'static [ColorSlot] {
+        return match (self.uses_color1(), self.uses_color2()) {
+            (true, true) => &[ColorSlot::Color1, ColorSlot::Color2],
+            (true, false) => &[ColorSlot::Color1],
+            (false, true) => &[ColorSlot::Color2],
+            (false, false) => &[],
+        };
+    }
+
+    /// Check if this pattern needs any user-configured colors to be set up first
+    pub fn requires_color_setup(&self) -> bool {
+        return !self.required_color_slots().is_empty();
+    }
+
+    /// Get the approximate sRGB color of a solid-color pattern, or `None` for animated patterns.
+    ///
+    /// The user manual only names the solid colors, so these values follow the
+    /// same-named web colors (which is also why `DarkGray` is lighter than `Gray`)
+    pub fn rgb(&self) -> Option<(u8, u8, u8)> {
+        return match self {
+            Pattern::HotPink => Some((255, 105, 180)),
+            Pattern::DarkRed => Some((139, 0, 0)),
+            Pattern::Red => Some((255, 0, 0)),
+            Pattern::RedOrange => Some((255, 69, 0)),
+            Pattern::Orange => Some((255, 165, 0)),
+            Pattern::Gold => Some((255, 215, 0)),
+            Pattern::Yellow => Some((255, 255, 0)),
+            Pattern::LawnGreen => Some((124, 252, 0)),
+            Pattern::Lime => Some((0, 255, 0)),
+            Pattern::DarkGreen => Some((0, 100, 0)),
+            Pattern::Green => Some((0, 128, 0)),
+            Pattern::BlueGreen => Some((13, 152, 186)),
+            Pattern::Aqua => Some((0, 255, 255)),
+            Pattern::SkyBlue => Some((135, 206, 235)),
+            Pattern::DarkBlue => Some((0, 0, 139)),
+            Pattern::Blue => Some((0, 0, 255)),
+            Pattern::BlueViolet => Some((138, 43, 226)),
+            Pattern::Violet => Some((238, 130, 238)),
+            Pattern::White => Some((255, 255, 255)),
+            Pattern::Gray => Some((128, 128, 128)),
+            Pattern::DarkGray => Some((169, 169, 169)),
+            Pattern::Black => Some((0, 0, 0)),
+            _ => None,
+        };
+    }
+
+    /// Get the gamma-corrected sRGB color of a solid-color pattern, or `None` for animated patterns.
+    ///
+    /// Each channel of [`Pattern::rgb()`] is mapped through `255 * (c / 255) ^ gamma`. A `gamma`
+    /// of around `2.2` is a good starting point for mirroring colors onto addressable LEDs, and
+    /// `1.0` leaves the colors unchanged
+    pub fn rgb_gamma(&self, gamma: f32) -> Option<(u8, u8, u8)> {
+        let correct = |channel: u8| {
+            let corrected = libm::powf(channel as f32 / 255.0, gamma) * 255.0;
+            return FloatCore::round(corrected).clamp(0.0, 255.0) as u8;
+        };
+        let (r, g, b) = self.rgb()?;
+        return Some((correct(r), correct(g), correct(b)));
+    }
+
+    /// Get the relative luminance of a solid-color pattern from `0.0` (black) to `1.0` (white),
+    /// or `None` for animated patterns.
+    ///
+    /// This is the WCAG relative luminance of [`Pattern::rgb()`], which linearizes the sRGB
+    /// channels before weighting them with the Rec.709 coefficients
+    pub fn luminance(&self) -> Option<f32> {
+        return Some(relative_luminance(self.rgb()?));
+    }
+
+    /// Get black or white, whichever is more readable as text over this solid-color pattern.
+    /// Returns `None` for animated patterns.
+    ///
+    /// This uses the WCAG relative luminance of [`Pattern::rgb()`], picking black text above
+    /// the `0.179` luminance where both give equal contrast
+    pub fn contrast_color(&self) -> Option<(u8, u8, u8)> {
+        if self.luminance()? > 0.179 {
+            return Some((0, 0, 0));
+        }
+        return Some((255, 255, 255));
+    }
+
+    /// Get the approximate HSV color of a solid-color pattern, or `None` for animated patterns.
+    ///
+    /// Hue is in degrees from `0.0..360.0`, saturation and value are from `0.0..=1.0`.
+    /// These are derived from [`Pattern::rgb()`], and colors without saturation have a hue of `0.0`
+    pub fn hsv(&self) -> Option<(f32, f32, f32)> {
+        let (r, g, b) = self.rgb()?;
+        let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta)
+        } else if max == g {
+            60.0 * (((b - r) / delta) + 2.0)
+        } else {
+            60.0 * (((r - g) / delta) + 4.0)
+        };
+        let hue = if hue < 0.0 { hue + 360.0 } else { hue };
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
+        return Some((hue, saturation, max));
+    }
+
+    /// Find the solid-color pattern whose [`Pattern::rgb()`] is closest to the given color.
+    ///
+    /// Distance is measured as Euclidean distance in RGB space. Ties go to the first
+    /// pattern in declaration order
+    pub fn nearest_solid_color(r: u8, g: u8, b: u8) -> Pattern {
+        let distance = |(pr, pg, pb): (u8, u8, u8)| {
+            let dr = (pr as i32 - r as i32).pow(2);
+            let dg = (pg as i32 - g as i32).pow(2);
+            let db = (pb as i32 - b as i32).pow(2);
+            return dr + dg + db;
+        };
+        return Pattern::all()
+            .filter_map(|pattern| pattern.rgb().map(|rgb| (pattern, distance(rgb))))
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(pattern, _)| pattern)
+            .unwrap();
+    }
+
+    /// Get the solid-color pattern closest to the complement of this one, or `None` for
+    /// animated patterns.
+    ///
+    /// The complement is [`Pattern::hsv()`] with the hue rotated by 180°, which for an RGB
+    /// color is each channel reflected about the midpoint of its largest and smallest
+    /// channels. The result is snapped with [`Pattern::nearest_solid_color()`], so colors
+    /// without saturation (the grays, `White` and `Black`) are their own complement
+    pub fn complement(&self) -> Option<Pattern> {
+        let (r, g, b) = self.rgb()?;
+        let sum = r.max(g).max(b) as u16 + r.min(g).min(b) as u16;
+        let reflect = |channel: u8| (sum - channel as u16) as u8;
+        return Some(Pattern::nearest_solid_color(reflect(r), reflect(g), reflect(b)));
+    }
+
     /// Get the pattern duty cycle as a percentage value from `-1.0` to `1.0`
-    pub fn as_percentage(&self) -> f32 {
-        return ((*self as u8) as f32 - 100.0) / 100.0;
+    pub const fn as_percentage(&self) -> f32 {
+        return (self.as_raw() as f32 - 100.0) / 100.0;
+    }
+
+    /// Get the pattern duty cycle in thousandths, from `-1000` to `1000`.
+    ///
+    /// This is [`Pattern::as_percentage()`] computed with integer math only, for targets without an FPU
+    pub const fn as_millipercentage(&self) -> i32 {
+        return (self.as_raw() as i32 - 100) * 10;
+    }
+
+    /// Find the pattern closest to a duty cycle percentage from `-1.0` to `1.0`
+    ///
+    /// The value is snapped to the nearest odd raw value used by the enum. Values
+    /// that land exactly halfway between two patterns (an even raw value) are
+    /// rounded up to the higher pattern, so `0.0` resolves to [`Pattern::Color1Chase`].
+    /// Values outside the range are clamped to it, so `-1.0` resolves to [`Pattern::Rainbow`]
+    /// and `1.0` to [`Pattern::Black`].
+    ///
+    /// Returns `None` only if the value snaps to one of the undefined raw values (`33` or `89`)
+    pub fn from_percentage(value: f32) -> Option<Pattern> {
+        let raw = (value * 100.0) + 100.0;
+        let snapped = (FloatCore::round((raw - 1.0) / 2.0) * 2.0) + 1.0;
+        return Pattern::try_from(snapped.clamp(1.0, 199.0) as u8).ok();
+    }
+
+    /// Get the value to pass to a WPILib `Spark` (or `PWMSparkMax`) motor controller's `set()`.
+    ///
+    /// WPILib drives the driver as if it were a Spark, taking a `-1.0..1.0` value where `-1.0`
+    /// is the shortest pulse (`Rainbow` end of the table) and `1.0` the longest (`Black` end).
+    /// This is the same value as [`Pattern::as_percentage()`]
+    pub const fn as_spark_set_value(&self) -> f32 {
+        return self.as_percentage();
+    }
+
+    /// Find the pattern selected by a WPILib `Spark` set value from `-1.0` to `1.0`.
+    ///
+    /// This snaps values exactly like [`Pattern::from_percentage()`]
+    pub fn from_spark_set_value(value: f32) -> Option<Pattern> {
+        return Pattern::from_percentage(value);
     }
 
     /// Get the pattern duty cycle as a percentage value from `0.0` to `1.0`
-    pub fn as_abs_percentage(&self) -> f32 {
+    pub const fn as_abs_percentage(&self) -> f32 {
         return (self.as_percentage() + 1.0) / 2.0;
     }
 
+    /// Find the pattern closest to an absolute duty cycle percentage from `0.0` to `1.0`.
+    ///
+    /// This is the inverse of [`Pattern::as_abs_percentage()`]. The value is snapped to the
+    /// nearest defined pattern (ties go to the higher pattern), so values landing in a gap
+    /// still resolve. Returns `None` if the value is outside `0.0..=1.0`
+    pub fn from_abs_percentage(value: f32) -> Option<Pattern> {
+        if !(0.0..=1.0).contains(&value) {
+            return None;
+        }
+        return Some(Pattern::nearest_to_raw(value * 200.0));
+    }
+
     /// Get the pattern duty cycle as a value from `0` to `max_duty`.
     ///
     /// The `max_duty` should be the output of [`embedded_hal::PwmPin::get_max_duty()`](https://docs.rs/embedded-hal/0.2.4/embedded_hal/trait.PwmPin.html#tymethod.get_max_duty)
+    ///
+    /// The scaling is done in `f32`, which only has 24 bits of precision. For a `max_duty`
+    /// above `2^24` the result is no longer exact; use [`Pattern::as_duty_precise()`] instead
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_duty` can not be converted to and from `f32`. See [`Pattern::try_as_duty()`]
+    /// for a non-panicking version
     pub fn as_duty<T: Num + NumCast + PartialOrd + Copy>(&self, max_duty: T) -> T {
-        let max_as_float: f32 = NumCast::from(max_duty).unwrap();
-        return (max_duty / max_duty)
-            * NumCast::from(self.as_abs_percentage() * max_as_float).unwrap();
+        return self.try_as_duty(max_duty).unwrap();
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Check if a PWM output with a maximum duty of `max_duty` can give every pattern its
+    /// own duty value.
+    ///
+    /// Neighbouring patterns are `1%` of the duty range apart, so this needs at least `100`
+    /// steps. Below that, some patterns truncate to the same duty as their neighbour and can
+    /// not be selected
+    pub const fn resolution_adequate(max_duty: u32) -> bool {
+        return max_duty >= 100;
+    }
 
-    #[test]
-    fn as_percentage_test() {
-        assert_eq!(Pattern::FireMedium.as_percentage(), -0.59);
-        assert_eq!(Pattern::Aqua.as_percentage(), 0.81);
+    /// Get the pattern duty cycle as a value from `0` to `max_duty`, rounded with `mode`.
+    ///
+    /// [`Pattern::as_duty()`] always truncates, which can bias outputs low. With a coarse
+    /// timer (see [`Pattern::resolution_adequate()`]), rounding to [`RoundingMode::Nearest`]
+    /// keeps each duty as close as possible to its pattern, but neighbouring patterns will
+    /// still share duties, as rounding can not add resolution
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_duty` can not be converted to and from `f32`. See
+    /// [`Pattern::try_as_duty_rounded()`] for a non-panicking version
+    pub fn as_duty_rounded<T: Num + NumCast + PartialOrd + Copy>(
+        &self,
+        max_duty: T,
+        mode: RoundingMode,
+    ) -> T {
+        return self.try_as_duty_rounded(max_duty, mode).unwrap();
     }
 
-    #[test]
-    fn as_abs_percentage_test() {
-        assert_eq!(Pattern::Color1Larson.as_abs_percentage(), 0.495);
-        assert_eq!(Pattern::Color1Chase.as_abs_percentage(), 0.505);
+    /// Get the pattern duty cycle as a value from `0` to `max_duty` rounded with `mode`, or
+    /// `None` if `max_duty` can not be converted to and from `f32`
+    ///
+    /// Like [`Pattern::try_as_duty()`], the result is clamped to `0..=max_duty`, and a
+    /// `max_duty` of `0` or below always gives a duty of `0`
+    pub fn try_as_duty_rounded<T: Num + NumCast + PartialOrd + Copy>(
+        &self,
+        max_duty: T,
+        mode: RoundingMode,
+    ) -> Option<T> {
+        return self.scale_duty(max_duty, |duty| match mode {
+            RoundingMode::Nearest => FloatCore::round(duty),
+            RoundingMode::Floor => FloatCore::floor(duty),
+            RoundingMode::Ceil => FloatCore::ceil(duty),
+        });
     }
 
-    #[test]
-    fn as_duty_test() {
-        assert_eq!(Pattern::Color1Larson.as_duty(u8::MAX), 126);
+    /// Get the pattern duty cycle as a value from `0` to `max_duty`, or `None` if
+    /// `max_duty` can not be converted to and from `f32`
+    ///
+    /// The result is clamped to `0..=max_duty`, so it is always a legal compare value, and is
+    /// never negative even for signed duty types. A `max_duty` of `0` or below (eg. from a timer
+    /// that has not been initialized yet) always gives a duty of `0`
+    pub fn try_as_duty<T: Num + NumCast + PartialOrd + Copy>(&self, max_duty: T) -> Option<T> {
+        // The f32 duty is cast straight back to the caller's duty type.
+        // For integer types this cast truncates towards zero
+        return self.scale_duty(max_duty, |duty| duty);
+    }
+
+    /// Scale `max_duty` by this pattern's duty cycle in f32, apply `round`, and clamp the
+    /// result to `0..=max_duty` before casting it back to the caller's duty type
+    fn scale_duty<T: Num + NumCast + PartialOrd + Copy>(
+        &self,
+        max_duty: T,
+        round: impl Fn(f32) -> f32,
+    ) -> Option<T> {
+        if max_duty <= T::zero() {
+            return Some(T::zero());
+        }
+
+        let max_as_float: f32 = NumCast::from(max_duty)?;
+        // Values past `f32::MAX` (eg. `u128::MAX`) convert to infinity, which can not scale
+        if !max_as_float.is_finite() {
+            return None;
+        }
+        let duty = round(self.as_abs_percentage() * max_as_float);
+
+        // Rounding must never push the duty out of range. This is checked before casting,
+        // as `max_as_float` itself may round past the largest value of `T`
+        if duty >= max_as_float {
+            return Some(max_duty);
+        }
+        if duty <= 0.0 {
+            return Some(T::zero());
+        }
+        return NumCast::from(duty);
+    }
+
+    /// Get the pattern duty cycle as a value from `0` to `max_duty` using integer math only.
+    ///
+    /// Like [`Pattern::as_duty()`], the result is truncated. The multiply is done in `u64`,
+    /// so any `max_duty` is safe
+    pub const fn as_duty_int(&self, max_duty: u32) -> u32 {
+        let abs_millipercentage = (self.as_millipercentage() + 1000) as u64;
+        return ((max_duty as u64 * abs_millipercentage) / 2000) as u32;
+    }
+
+    /// Get the pattern duty cycle as a value from `0` to `max_duty` without losing precision,
+    /// for high-resolution timers.
+    ///
+    /// This uses integer math only (widening to `u128`), so the result is the exact truncated
+    /// duty for any `max_duty`, unlike the `f32`-based [`Pattern::as_duty()`]
+    pub const fn as_duty_precise(&self, max_duty: u64) -> u64 {
+        return ((max_duty as u128 * self.as_raw() as u128) / 200) as u64;
+    }
+
+    /// Get this pattern's duty cycle from `0` to `max_duty`, scaled by `brightness`.
+    ///
+    /// This is intended for a separate PWM dimmer channel (eg. one switching the LED strip's
+    /// power), and does NOT change which pattern is selected on the driver. Sending the scaled
+    /// value to the driver itself would select a different pattern. `brightness` is clamped
+    /// to `0.0..=1.0`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_duty` can not be converted to and from `f32`
+    pub fn as_duty_scaled<T: Num + NumCast + PartialOrd + Copy>(
+        &self,
+        max_duty: T,
+        brightness: f32,
+    ) -> T {
+        let brightness = if brightness > 0.0 { brightness.min(1.0) } else { 0.0 };
+        let duty: f32 = NumCast::from(self.as_duty(max_duty)).unwrap();
+        return NumCast::from(duty * brightness).unwrap();
+    }
+
+    /// Find the pattern closest to a duty cycle of `duty` out of `max_duty`.
+    ///
+    /// This is the inverse of [`Pattern::as_duty()`]. Unlike [`Pattern::from_percentage()`]
+    /// this always returns a pattern, snapping undefined values to the nearest defined one.
+    /// Ties between two patterns are broken towards the higher one, and a `max_duty` of `0`
+    /// is treated as a duty cycle of `0`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `duty` or `max_duty` can not be converted to `f32`
+    pub fn closest_to_duty<T: Num + NumCast + PartialOrd + Copy>(duty: T, max_duty: T) -> Pattern {
+        let duty_as_float: f32 = NumCast::from(duty).unwrap();
+        let max_as_float: f32 = NumCast::from(max_duty).unwrap();
+        let raw = if max_duty.is_zero() {
+            0.0
+        } else {
+            (duty_as_float / max_as_float) * 200.0
+        };
+
+        return Pattern::nearest_to_raw(raw);
+    }
+
+    /// Find the defined pattern closest to a (possibly fractional) raw value, breaking ties
+    /// towards the higher pattern
+    fn nearest_to_raw(raw: f32) -> Pattern {
+        let mut closest = Pattern::FIRST;
+        for pattern in Pattern::all() {
+            let distance = (pattern.as_raw() as f32 - raw).abs();
+            if distance <= (closest.as_raw() as f32 - raw).abs() {
+                closest = pattern;
+            }
+        }
+        return closest;
+    }
+
+    /// Set `pin` to this pattern's duty cycle, scaled to the pin's maximum duty cycle.
+    ///
+    /// Requires the `eh1` feature
+    #[cfg(feature = "eh1")]
+    pub fn apply<P: eh1::pwm::SetDutyCycle>(&self, pin: &mut P) -> Result<(), P::Error> {
+        let duty = self.as_duty(pin.max_duty_cycle());
+        return pin.set_duty_cycle(duty);
+    }
+
+    /// Set `pin` to this pattern's duty cycle, scaled to the pin's maximum duty.
+    ///
+    /// This assumes the pin has already been enabled. Requires the `eh02` feature
+    #[cfg(feature = "eh02")]
+    pub fn apply_02<P: eh02::PwmPin>(&self, pin: &mut P)
+    where
+        P::Duty: Num + NumCast + PartialOrd + Copy,
+    {
+        pin.set_duty(self.as_duty(pin.get_max_duty()));
+    }
+
+    /// Set `channel` of the multi-channel `pwm` peripheral to this pattern's duty cycle,
+    /// scaled to the peripheral's maximum duty.
+    ///
+    /// This is for boards where the driver shares a timer with other outputs. It assumes
+    /// the channel has already been enabled. Requires the `eh02` feature
+    #[cfg(feature = "eh02")]
+    pub fn apply_channel<P: eh02::Pwm>(&self, pwm: &mut P, channel: P::Channel)
+    where
+        P::Duty: Num + NumCast + PartialOrd + Copy,
+    {
+        pwm.set_duty(channel, self.as_duty(pwm.get_max_duty()));
+    }
+
+    /// Get the pattern as a raw PWM pulse width in microseconds.
+    ///
+    /// The driver expects pulses from `1000µs` to `2000µs`, with each pattern
+    /// placed `5µs` per raw value above `1000µs` (`Rainbow` is `1005µs`, `Black` is `1995µs`)
+    pub const fn as_pulse_width_us(&self) -> u16 {
+        return 1000 + ((self.as_raw() as u16) * 5);
+    }
+
+    /// Get the `(pulse_width_us, period_us)` pair a timer needs to output this pattern.
+    ///
+    /// The period is always [`Pattern::FRAME_PERIOD_US`], as the driver expects a standard
+    /// 50Hz servo-style PWM frame
+    pub const fn as_blinkin_frame(&self) -> (u16, u16) {
+        return (self.as_pulse_width_us(), Pattern::FRAME_PERIOD_US);
+    }
+
+    /// Get the pattern's PWM pulse width as a `fugit` duration, for use with timer APIs.
+    ///
+    /// Requires the `fugit` feature
+    ///
+    /// ```
+    /// # use rev_11_1105_rs::Pattern;
+    /// let pulse_width = Pattern::Rainbow.pulse_width();
+    /// assert_eq!(pulse_width.to_micros(), 1005);
+    /// // eg. pwm_timer.start(pulse_width)
+    /// ```
+    #[cfg(feature = "fugit")]
+    pub const fn pulse_width(&self) -> fugit::MicrosDurationU32 {
+        return fugit::MicrosDurationU32::from_ticks(self.as_pulse_width_us() as u32);
+    }
+
+    /// Get the pattern as a raw PWM pulse width in nanoseconds.
+    ///
+    /// This is [`Pattern::as_pulse_width_us()`] scaled by `1000`
+    pub fn as_pulse_width_ns(&self) -> u32 {
+        return self.as_pulse_width_us() as u32 * 1000;
+    }
+
+    /// Get the timer compare value that outputs this pattern's pulse width, for a timer
+    /// that counts to `timer_top` once every `period_us` microseconds.
+    ///
+    /// The result is truncated, like [`Pattern::as_duty_int()`]. If the pulse width does not
+    /// fit in the period (including a `period_us` of `0`), the result is clamped to `timer_top`
+    pub const fn as_compare_value(&self, timer_top: u32, period_us: u32) -> u32 {
+        let pulse_width_us = self.as_pulse_width_us() as u32;
+        if pulse_width_us >= period_us {
+            return timer_top;
+        }
+        return ((timer_top as u64 * pulse_width_us as u64) / period_us as u64) as u32;
+    }
+
+    /// Find the pattern selected by a PWM pulse width in microseconds.
+    ///
+    /// Patterns are spaced `10µs` apart, so each one accepts pulses from `5µs` below
+    /// its nominal width up to `4µs` above it (`Rainbow` accepts `1000..=1009µs`).
+    /// Widths in the slot of an undefined raw value snap to the nearest defined pattern
+    /// (ties go to the higher one), so only widths outside `1000..2000µs` return `None`
+    pub fn from_pulse_width_us(us: u16) -> Option<Pattern> {
+        if !(1000..2000).contains(&us) {
+            return None;
+        }
+        return Some(Pattern::nearest_valid_pulse_width_us(us).0);
+    }
+
+    /// Find the pattern whose ideal pulse width is closest to `us`, along with how far `us`
+    /// is from that ideal width (`us - ideal`).
+    ///
+    /// Unlike [`Pattern::from_pulse_width_us()`], this always returns a pattern, which makes
+    /// it useful for diagnosing miscalibrated timers. Ties go to the higher pattern, and the
+    /// delta saturates at the bounds of `i16`
+    pub fn nearest_valid_pulse_width_us(us: u16) -> (Pattern, i16) {
+        let pattern = Pattern::nearest_to_raw((us as f32 - 1000.0) / 5.0);
+        let delta = us as i32 - pattern.as_pulse_width_us() as i32;
+        return (pattern, delta.clamp(i16::MIN as i32, i16::MAX as i32) as i16);
+    }
+
+    /// Write every pattern as a CSV line of `name,raw,percentage,pulse_us`, in declaration order.
+    ///
+    /// This does not allocate, so it works with any [`fmt::Write`] sink such as a
+    /// `heapless::String` or a UART writer. No header line is written.
+    /// Requires the `names` feature
+    #[cfg(feature = "names")]
+    pub fn write_table<W: fmt::Write>(w: &mut W) -> fmt::Result {
+        for pattern in Pattern::all() {
+            writeln!(
+                w,
+                "{},{},{:.2},{}",
+                pattern.name(),
+                pattern.as_raw(),
+                pattern.as_percentage(),
+                pattern.as_pulse_width_us()
+            )?;
+        }
+        return Ok(());
+    }
+
+    /// Get this pattern as a JSON object of its variant name and raw value, eg.
+    /// `{"name":"Rainbow","value":1}`, without allocating.
+    ///
+    /// This is for serving the pattern catalog from firmware. To serialize `Pattern` itself
+    /// without an allocator, use the `serde` feature with a `no_std` serializer such as
+    /// `serde-json-core`. Requires the `heapless` and `names` features
+    ///
+    /// `N` must be at least [`Pattern::JSON_ENTRY_MAX_LEN`], so every pattern fits. This is
+    /// checked at compile time:
+    ///
+    /// ```compile_fail
+    /// let entry = rev_11_1105_rs::Pattern::Rainbow.to_json_entry::<16>();
+    /// ```
+    #[cfg(all(feature = "heapless", feature = "names"))]
+    pub fn to_json_entry<const N: usize>(&self) -> heapless::String<N> {
+        use core::fmt::Write;
+
+        const {
+            assert!(
+                N >= Pattern::JSON_ENTRY_MAX_LEN,
+                "`N` is too small to hold every JSON entry"
+            )
+        };
+
+        let mut entry = heapless::String::new();
+        // Can not fail, as `N` was checked against the longest possible entry
+        let _ = write!(
+            entry,
+            "{{\"name\":\"{}\",\"value\":{}}}",
+            self.name(),
+            self.as_raw()
+        );
+        return entry;
+    }
+}
+
+impl Default for Pattern {
+    /// Defaults to [`Pattern::Black`], which turns the LEDs off as the safest power-on state
+    fn default() -> Self {
+        return Pattern::Black;
+    }
+}
+
+impl From<Pattern> for f32 {
+    /// Convert a pattern to its duty cycle percentage from `-1.0` to `1.0`. See [`Pattern::as_percentage()`]
+    fn from(pattern: Pattern) -> Self {
+        return pattern.as_percentage();
+    }
+}
+
+impl From<Pattern> for u8 {
+    /// Convert a pattern to its raw datasheet value. See [`Pattern::as_raw()`]
+    fn from(pattern: Pattern) -> Self {
+        return pattern.as_raw();
+    }
+}
+
+impl From<Pattern> for u16 {
+    /// Convert a pattern to its raw datasheet value, widened to `u16`. See [`Pattern::as_raw()`]
+    fn from(pattern: Pattern) -> Self {
+        return pattern.as_raw() as u16;
+    }
+}
+
+impl From<Pattern> for u32 {
+    /// Convert a pattern to its raw datasheet value, widened to `u32`. See [`Pattern::as_raw()`]
+    fn from(pattern: Pattern) -> Self {
+        return pattern.as_raw() as u32;
+    }
+}
+
+impl core::ops::Add<i32> for Pattern {
+    type Output = Pattern;
+
+    /// Move `rhs` patterns forward in declaration order, wrapping from `Black` back to
+    /// `Rainbow` (and the other way for negative `rhs`). See [`Pattern::step_by()`]
+    fn add(self, rhs: i32) -> Self::Output {
+        return self.step_by(rhs);
+    }
+}
+
+impl core::ops::Sub<i32> for Pattern {
+    type Output = Pattern;
+
+    /// Move `rhs` patterns backward in declaration order, wrapping from `Rainbow` back to
+    /// `Black` (and the other way for negative `rhs`). See [`Pattern::step_by()`]
+    fn sub(self, rhs: i32) -> Self::Output {
+        // Reduce first so negating `i32::MIN` can not overflow
+        return self.step_by(-(rhs % Pattern::COUNT as i32));
+    }
+}
+
+/// Without the `names` feature, patterns are debug-printed as their raw value (eg. `Pattern(5)`)
+#[cfg(not(feature = "names"))]
+impl fmt::Debug for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.debug_tuple("Pattern").field(&self.as_raw()).finish();
+    }
+}
+
+#[cfg(feature = "names")]
+impl fmt::Display for Pattern {
+    /// Write the pattern's name as it appears in the user manual's color table.
+    ///
+    /// See [`Pattern::display_name()`]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.write_str(self.display_name());
+    }
+}
+
+/// Write the pattern's variant name with `ufmt`, matching its [`fmt::Debug`] output.
+///
+/// Requires the `ufmt` feature
+///
+/// ```
+/// use rev_11_1105_rs::Pattern;
+///
+/// struct Buffer([u8; 32], usize);
+///
+/// impl ufmt::uWrite for Buffer {
+///     type Error = ();
+///
+///     fn write_str(&mut self, s: &str) -> Result<(), ()> {
+///         let end = self.1 + s.len();
+///         self.0.get_mut(self.1..end).ok_or(())?.copy_from_slice(s.as_bytes());
+///         self.1 = end;
+///         return Ok(());
+///     }
+/// }
+///
+/// let mut buffer = Buffer([0; 32], 0);
+/// ufmt::uwrite!(buffer, "{:?}", Pattern::RainbowForest).unwrap();
+/// assert_eq!(&buffer.0[..buffer.1], b"RainbowForest");
+/// ```
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for Pattern {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        return f.write_str(self.name());
+    }
+}
+
+/// Write the pattern's name with `ufmt`, matching its [`fmt::Display`] output.
+///
+/// See [`Pattern::display_name()`]. Requires the `ufmt` feature
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for Pattern {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        return f.write_str(self.display_name());
+    }
+}
+
+/// Error returned when a raw value does not correspond to any defined [`Pattern`]
+///
+/// The offending value is kept (in the type it was given as) so it can be reported back to the caller
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidPatternValue<T = u8>(pub T);
+
+impl<T: fmt::Display> fmt::Display for InvalidPatternValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "invalid pattern value: {}", self.0);
+    }
+}
+
+impl TryFrom<u8> for Pattern {
+    type Error = InvalidPatternValue;
+
+    /// Convert a raw value from `0..200` back into its [`Pattern`]
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        return DECODE_TABLE[value as usize].ok_or(InvalidPatternValue(value));
+    }
+}
+
+impl TryFrom<u16> for Pattern {
+    type Error = InvalidPatternValue<u16>;
+
+    /// Convert a raw value from `0..200` back into its [`Pattern`]
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        return u8::try_from(value)
+            .ok()
+            .and_then(|raw| Pattern::try_from(raw).ok())
+            .ok_or(InvalidPatternValue(value));
+    }
+}
+
+impl TryFrom<i32> for Pattern {
+    type Error = InvalidPatternValue<i32>;
+
+    /// Convert a raw value from `0..200` back into its [`Pattern`]
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        return u8::try_from(value)
+            .ok()
+            .and_then(|raw| Pattern::try_from(raw).ok())
+            .ok_or(InvalidPatternValue(value));
+    }
+}
+
+/// A raw pattern byte that is known to be valid.
+///
+/// This is the same size as a `u8`, so it can be passed around as cheaply as the raw value,
+/// and converted to a full [`Pattern`] only when needed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct PatternByte(u8);
+
+impl PatternByte {
+    /// Wrap `value` if it is the raw value of a defined pattern. See [`Pattern::is_valid_raw()`]
+    pub const fn new(value: u8) -> Option<Self> {
+        if !Pattern::is_valid_raw(value) {
+            return None;
+        }
+        return Some(PatternByte(value));
+    }
+
+    /// Get the raw byte
+    pub const fn get(&self) -> u8 {
+        return self.0;
+    }
+
+    /// Get the pattern this byte represents
+    pub fn pattern(&self) -> Pattern {
+        return DECODE_TABLE[self.0 as usize].unwrap();
+    }
+}
+
+impl From<Pattern> for PatternByte {
+    fn from(pattern: Pattern) -> Self {
+        return PatternByte(pattern.as_raw());
+    }
+}
+
+impl From<PatternByte> for Pattern {
+    fn from(byte: PatternByte) -> Self {
+        return byte.pattern();
+    }
+}
+
+/// Toggles the strip between a remembered pattern and [`Pattern::off()`].
+///
+/// Selecting a new pattern with [`PatternToggle::set()`] updates the remembered pattern,
+/// unless the new pattern is itself off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatternToggle {
+    remembered: Pattern,
+    current: Pattern,
+}
+
+impl PatternToggle {
+    /// Create a toggle that is currently showing `pattern`, or `None` if `pattern` is off.
+    ///
+    /// An off pattern is rejected, as there would be nothing to toggle back on to
+    pub const fn new(pattern: Pattern) -> Option<Self> {
+        if matches!(pattern, Pattern::Black) {
+            return None;
+        }
+        return Some(PatternToggle {
+            remembered: pattern,
+            current: pattern,
+        });
+    }
+
+    /// Get the pattern that should currently be shown
+    pub const fn current(&self) -> Pattern {
+        return self.current;
+    }
+
+    /// Show `pattern`, remembering it for later toggles if it is not off
+    pub fn set(&mut self, pattern: Pattern) {
+        if !pattern.is_off() {
+            self.remembered = pattern;
+        }
+        self.current = pattern;
+    }
+
+    /// Switch between off and the remembered pattern, returning the pattern to show
+    pub fn toggle(&mut self) -> Pattern {
+        if self.current.is_off() {
+            self.current = self.remembered;
+        } else {
+            self.current = Pattern::off();
+        }
+        return self.current;
+    }
+}
+
+/// Error returned when a string does not name any defined [`Pattern`].
+///
+/// Requires the `names` feature
+#[cfg(feature = "names")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsePatternError {
+    suggestion: Option<&'static str>,
+}
+
+#[cfg(feature = "names")]
+impl ParsePatternError {
+    /// Get the pattern name closest to the string that failed to parse, if any was close enough
+    pub fn suggestion(&self) -> Option<&'static str> {
+        return self.suggestion;
+    }
+}
+
+#[cfg(feature = "names")]
+impl fmt::Display for ParsePatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown pattern name")?;
+        if let Some(suggestion) = self.suggestion {
+            write!(f, ", did you mean `{}`?", suggestion)?;
+        }
+        return Ok(());
+    }
+}
+
+/// Longest string that will be compared against pattern names when looking for a suggestion
+#[cfg(feature = "names")]
+const MAX_SUGGESTION_LEN: usize = 32;
+
+/// Case-insensitive Levenshtein distance between two strings of up to `MAX_SUGGESTION_LEN` bytes
+#[cfg(feature = "names")]
+fn edit_distance(a: &[u8], b: &[u8]) -> Option<usize> {
+    if a.len() > MAX_SUGGESTION_LEN || b.len() > MAX_SUGGESTION_LEN {
+        return None;
+    }
+
+    let mut row = [0; MAX_SUGGESTION_LEN + 1];
+    for (j, cell) in row.iter_mut().enumerate().take(b.len() + 1) {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        let mut diagonal = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let above = row[j];
+            let cost = if a[i - 1].eq_ignore_ascii_case(&b[j - 1]) { 0 } else { 1 };
+            row[j] = (above + 1).min(row[j - 1] + 1).min(diagonal + cost);
+            diagonal = above;
+        }
+    }
+    return Some(row[b.len()]);
+}
+
+#[cfg(feature = "names")]
+impl FromStr for Pattern {
+    type Err = ParsePatternError;
+
+    /// Parse a pattern from its exact (case-sensitive) variant name, eg. `"RainbowForest"`.
+    ///
+    /// On failure, the error suggests the closest variant name if it is within a few edits
+    /// (ignoring case) of the input
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(pattern) = Pattern::all().find(|pattern| pattern.name() == s) {
+            return Ok(pattern);
+        }
+
+        let suggestion = Pattern::all()
+            .filter_map(|pattern| {
+                let distance = edit_distance(s.as_bytes(), pattern.name().as_bytes())?;
+                return Some((pattern.name(), distance));
+            })
+            // Allow roughly one edit for every three characters of the name
+            .filter(|(name, distance)| *distance <= (name.len() / 3).max(1))
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(name, _)| name);
+        return Err(ParsePatternError { suggestion });
+    }
+}
+
+#[cfg(feature = "names")]
+impl TryFrom<&str> for Pattern {
+    type Error = ParsePatternError;
+
+    /// Leniently parse a pattern from either its variant name (eg. `"RainbowForest"`) or
+    /// its datasheet name (eg. `"Rainbow, Forest Palette"`), ignoring surrounding whitespace.
+    ///
+    /// On failure, the error suggests the closest variant name as with [`FromStr`]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+        if let Some(pattern) = Pattern::all().find(|pattern| pattern.display_name() == value) {
+            return Ok(pattern);
+        }
+        return value.parse();
+    }
+}
+
+/// Wrapper around a [`Pattern`] that (de)serializes as its single-byte raw value
+/// instead of its variant name.
+///
+/// Deserialization rejects any value that is not a defined pattern. Requires the `serde` feature
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompactPattern(pub Pattern);
+
+#[cfg(feature = "serde")]
+impl From<Pattern> for CompactPattern {
+    fn from(pattern: Pattern) -> Self {
+        return CompactPattern(pattern);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<CompactPattern> for Pattern {
+    fn from(compact: CompactPattern) -> Self {
+        return compact.0;
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CompactPattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_u8(self.0.as_raw());
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CompactPattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = <u8 as serde::Deserialize>::deserialize(deserializer)?;
+        return Pattern::try_from(raw)
+            .map(CompactPattern)
+            .map_err(serde::de::Error::custom);
+    }
+}
+
+/// Looping sequence of patterns, each shown for a set duration.
+///
+/// Holds up to `N` entries without allocating. Requires the `heapless` feature
+#[cfg(feature = "heapless")]
+#[derive(Debug, Clone, Default)]
+pub struct Playlist<const N: usize> {
+    entries: heapless::Vec<(Pattern, u32), N>,
+    position_ms: u64,
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> Playlist<N> {
+    /// Create an empty playlist
+    pub const fn new() -> Self {
+        return Playlist {
+            entries: heapless::Vec::new(),
+            position_ms: 0,
+        };
+    }
+
+    /// Add `pattern` to the end of the playlist, to be shown for `duration_ms`.
+    ///
+    /// Returns the entry back if the playlist is full
+    pub fn push(&mut self, pattern: Pattern, duration_ms: u32) -> Result<(), (Pattern, u32)> {
+        return self.entries.push((pattern, duration_ms));
+    }
+
+    /// Move the playlist forward by `elapsed_ms` and get the pattern that should now be active.
+    ///
+    /// The playlist loops back to its first entry once every entry has been shown.
+    /// Returns `None` if the playlist is empty
+    pub fn advance(&mut self, elapsed_ms: u32) -> Option<Pattern> {
+        let total_ms: u64 = self.entries.iter().map(|(_, duration)| *duration as u64).sum();
+        if total_ms == 0 {
+            return self.entries.first().map(|(pattern, _)| *pattern);
+        }
+        self.position_ms = (self.position_ms + elapsed_ms as u64) % total_ms;
+
+        let mut remaining_ms = self.position_ms;
+        for (pattern, duration) in &self.entries {
+            if remaining_ms < *duration as u64 {
+                return Some(*pattern);
+            }
+            remaining_ms -= *duration as u64;
+        }
+        return None;
+    }
+
+    /// Restart the playlist from its first entry
+    pub fn reset(&mut self) {
+        self.position_ms = 0;
+    }
+}
+
+/// Every variant name, in declaration order, for reporting unknown variants
+#[cfg(feature = "serde")]
+const VARIANT_NAMES: [&str; Pattern::COUNT] = {
+    let mut names = [""; Pattern::COUNT];
+    let mut i = 0;
+    while i < Pattern::COUNT {
+        names[i] = PATTERNS[i].name();
+        i += 1;
+    }
+    names
+};
+
+/// Deserializes a [`Pattern`] from its variant name, raw value, or (for binary formats) enum variant
+#[cfg(feature = "serde")]
+struct PatternVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for PatternVisitor {
+    type Value = Pattern;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.write_str("a pattern name or raw pattern value");
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Pattern, E> {
+        return value
+            .parse()
+            .map_err(|_| E::unknown_variant(value, &VARIANT_NAMES));
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Pattern, E> {
+        return u8::try_from(value)
+            .ok()
+            .and_then(|raw| Pattern::try_from(raw).ok())
+            .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Unsigned(value), &self));
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Pattern, E> {
+        return u8::try_from(value)
+            .ok()
+            .and_then(|raw| Pattern::try_from(raw).ok())
+            .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Signed(value), &self));
+    }
+
+    fn visit_enum<A: serde::de::EnumAccess<'de>>(self, data: A) -> Result<Pattern, A::Error> {
+        let (VariantIdentifier(pattern), variant) = data.variant()?;
+        serde::de::VariantAccess::unit_variant(variant)?;
+        return Ok(pattern);
+    }
+}
+
+/// Enum variant identifier, as either a variant name or a declaration index
+#[cfg(feature = "serde")]
+struct VariantIdentifier(Pattern);
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for VariantIdentifier {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IdentifierVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for IdentifierVisitor {
+            type Value = VariantIdentifier;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                return f.write_str("a pattern variant");
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<VariantIdentifier, E> {
+                return PatternVisitor.visit_str(value).map(VariantIdentifier);
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<VariantIdentifier, E> {
+                return usize::try_from(value)
+                    .ok()
+                    .and_then(|index| PATTERNS.get(index).copied())
+                    .map(VariantIdentifier)
+                    .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Unsigned(value), &self));
+            }
+        }
+
+        return deserializer.deserialize_identifier(IdentifierVisitor);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Pattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Binary formats can't tell a name from a raw value, so they get the usual enum encoding
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(PatternVisitor);
+        }
+        return deserializer.deserialize_enum("Pattern", &VARIANT_NAMES, PatternVisitor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_percentage_test() {
+        assert_eq!(Pattern::FireMedium.as_percentage(), -0.59);
+        assert_eq!(Pattern::Aqua.as_percentage(), 0.81);
+    }
+
+    #[test]
+    fn as_millipercentage_test() {
+        assert_eq!(Pattern::FireMedium.as_millipercentage(), -590);
+        assert_eq!(Pattern::Aqua.as_millipercentage(), 810);
+    }
+
+    #[test]
+    fn const_percentage_test() {
+        const TABLE: [f32; 2] = [Pattern::Red.as_percentage(), Pattern::Red.as_abs_percentage()];
+        assert_eq!(TABLE, [0.61, 0.805]);
+    }
+
+    #[test]
+    fn as_abs_percentage_test() {
+        assert_eq!(Pattern::Color1Larson.as_abs_percentage(), 0.495);
+        assert_eq!(Pattern::Color1Chase.as_abs_percentage(), 0.505);
+    }
+
+    #[test]
+    fn as_duty_test() {
+        assert_eq!(Pattern::Color1Larson.as_duty(u8::MAX), 126);
+        assert_eq!(Pattern::Color1Larson.as_duty(u8::MAX as u32), 126);
+        assert_eq!(Pattern::Color1Larson.as_duty(u8::MAX as f32), 126.225);
+    }
+
+    #[test]
+    fn eq_test() {
+        assert_eq!(Pattern::Red, Pattern::Red);
+        assert_ne!(Pattern::Red, Pattern::DarkRed);
+    }
+
+    #[test]
+    fn ord_test() {
+        assert!(Pattern::Rainbow < Pattern::Black);
+        assert!(Pattern::Aqua < Pattern::BlueViolet);
+
+        let mut patterns = [Pattern::Black, Pattern::Aqua, Pattern::Rainbow];
+        patterns.sort();
+        assert_eq!(patterns, [Pattern::Rainbow, Pattern::Aqua, Pattern::Black]);
+        assert_eq!(patterns.binary_search(&Pattern::Aqua), Ok(1));
+    }
+
+    #[test]
+    fn as_raw_test() {
+        assert_eq!(Pattern::Rainbow.as_raw(), 1);
+        assert_eq!(Pattern::Red.as_raw(), 161);
+        assert_eq!(Pattern::try_from(Pattern::Red.as_raw()), Ok(Pattern::Red));
+    }
+
+    #[test]
+    fn default_test() {
+        assert_eq!(Pattern::default(), Pattern::Black);
+        assert_eq!(Pattern::off(), Pattern::Black);
+        assert!(Pattern::off().is_off());
+        assert_eq!(Pattern::default_color(), Pattern::Blue);
+    }
+
+    #[test]
+    fn repr_test() {
+        assert_eq!(core::mem::size_of::<Pattern>(), 1);
+    }
+
+    #[test]
+    fn into_test() {
+        let percentage: f32 = Pattern::FireMedium.into();
+        let raw: u8 = Pattern::FireMedium.into();
+        assert_eq!(percentage, -0.59);
+        assert_eq!(raw, 41);
+    }
+
+    #[test]
+    fn try_from_u8_test() {
+        assert!(matches!(Pattern::try_from(1u8), Ok(Pattern::Rainbow)));
+        assert!(matches!(Pattern::try_from(199u8), Ok(Pattern::Black)));
+        assert_eq!(Pattern::try_from(2u8).unwrap_err(), InvalidPatternValue(2));
+        assert_eq!(Pattern::try_from(33u8).unwrap_err(), InvalidPatternValue(33));
+        assert_eq!(Pattern::try_from(200u8).unwrap_err(), InvalidPatternValue(200));
+    }
+
+    #[test]
+    fn from_percentage_test() {
+        let patterns = [
+            Pattern::Rainbow,
+            Pattern::FireMedium,
+            Pattern::Color1Larson,
+            Pattern::Aqua,
+            Pattern::Black,
+        ];
+        for pattern in &patterns {
+            let decoded = Pattern::from_percentage(pattern.as_percentage());
+            assert_eq!(decoded.map(|p| p as u8), Some(*pattern as u8));
+        }
+        assert!(matches!(Pattern::from_percentage(0.0), Some(Pattern::Color1Chase)));
+        assert!(matches!(Pattern::from_percentage(-0.655), Some(Pattern::BpmOcean)));
+        assert!(Pattern::from_percentage(-0.67).is_none());
+        assert!(Pattern::from_percentage(-0.11).is_none());
+        assert_eq!(Pattern::from_percentage(-1.0), Some(Pattern::Rainbow));
+        assert_eq!(Pattern::from_percentage(1.0), Some(Pattern::Black));
+        assert_eq!(Pattern::from_percentage(-1.5), Some(Pattern::Rainbow));
+        assert_eq!(Pattern::from_percentage(1.5), Some(Pattern::Black));
+    }
+
+    #[test]
+    fn as_pulse_width_us_test() {
+        assert_eq!(Pattern::Rainbow.as_pulse_width_us(), 1005);
+        assert_eq!(Pattern::Color1Chase.as_pulse_width_us(), 1505);
+        assert_eq!(Pattern::Black.as_pulse_width_us(), 1995);
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn from_str_test() {
+        assert!(matches!("RainbowForest".parse::<Pattern>(), Ok(Pattern::RainbowForest)));
+        assert!(matches!("Black".parse::<Pattern>(), Ok(Pattern::Black)));
+        assert!("rainbowforest".parse::<Pattern>().is_err());
+        assert!("".parse::<Pattern>().is_err());
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn display_test() {
+        use core::fmt::Write;
+
+        struct Buffer([u8; 64], usize);
+        impl Write for Buffer {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0[self.1..self.1 + s.len()].copy_from_slice(s.as_bytes());
+                self.1 += s.len();
+                return Ok(());
+            }
+        }
+
+        let mut buffer = Buffer([0; 64], 0);
+        write!(buffer, "{}|{}", Pattern::RainbowForest, Pattern::BlueViolet).unwrap();
+        assert_eq!(&buffer.0[..buffer.1], b"Rainbow, Forest Palette|Blue Violet");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        assert_eq!(serde_json::to_string(&Pattern::RainbowOcean).unwrap(), "\"RainbowOcean\"");
+        assert!(matches!(serde_json::from_str("\"RainbowOcean\""), Ok(Pattern::RainbowOcean)));
+        assert!(serde_json::from_str::<Pattern>("\"Plaid\"").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_raw_value_test() {
+        use serde_test::{assert_de_tokens, Configure, Token};
+
+        assert_eq!(serde_json::from_str::<Pattern>("\"Rainbow\"").unwrap(), Pattern::Rainbow);
+        assert_eq!(serde_json::from_str::<Pattern>("1").unwrap(), Pattern::Rainbow);
+        assert_eq!(serde_json::from_str::<Pattern>("161").unwrap(), Pattern::Red);
+        assert!(serde_json::from_str::<Pattern>("2").is_err());
+        assert!(serde_json::from_str::<Pattern>("256").is_err());
+        assert!(serde_json::from_str::<Pattern>("-1").is_err());
+
+        assert_de_tokens(
+            &Pattern::RainbowParty.compact(),
+            &[Token::Enum { name: "Pattern" }, Token::U32(1), Token::Unit],
+        );
+        assert_de_tokens(
+            &Pattern::RainbowParty.compact(),
+            &[Token::UnitVariant { name: "Pattern", variant: "RainbowParty" }],
+        );
+    }
+
+    #[test]
+    fn all_test() {
+        let mut iter = Pattern::all();
+        assert!(matches!(iter.next(), Some(Pattern::Rainbow)));
+        assert!(matches!(iter.next(), Some(Pattern::RainbowParty)));
+        assert!(matches!(iter.next_back(), Some(Pattern::Black)));
+
+        let mut previous = 0;
+        for pattern in Pattern::all() {
+            assert!(pattern as u8 > previous);
+            previous = pattern as u8;
+        }
+    }
+
+    #[test]
+    fn next_prev_test() {
+        assert!(matches!(Pattern::Rainbow.next(), Pattern::RainbowParty));
+        assert!(matches!(Pattern::BpmRainbow.next(), Pattern::BpmOcean));
+        assert!(matches!(Pattern::BpmOcean.prev(), Pattern::BpmRainbow));
+        assert!(matches!(Pattern::Black.next(), Pattern::Rainbow));
+        assert!(matches!(Pattern::Rainbow.prev(), Pattern::Black));
+    }
+
+    #[test]
+    fn category_test() {
+        let count = |category| Pattern::all().filter(|p| p.category() == category).count();
+        assert_eq!(count(Category::Rainbow), 6);
+        assert_eq!(count(Category::Shot), 3);
+        assert_eq!(count(Category::Bpm), 4);
+        assert_eq!(count(Category::Strobe), 3);
+        assert_eq!(count(Category::Color1), 10);
+        assert_eq!(count(Category::Color2), 10);
+        assert_eq!(count(Category::Color1And2), 10);
+        assert_eq!(count(Category::SolidColor), 22);
+
+        assert_eq!(Pattern::RainbowGlitter.category(), Category::Rainbow);
+        assert_eq!(Pattern::Color1Strobe.category(), Category::Color1);
+        assert_eq!(Pattern::Sinelon1And2.category(), Category::Color1And2);
+        assert_eq!(Pattern::HotPink.category(), Category::SolidColor);
+        assert_eq!(Pattern::Black.category(), Category::SolidColor);
+    }
+
+    #[test]
+    fn rgb_test() {
+        let expected = [
+            (Pattern::HotPink, (255, 105, 180)),
+            (Pattern::Red, (255, 0, 0)),
+            (Pattern::Lime, (0, 255, 0)),
+            (Pattern::Aqua, (0, 255, 255)),
+            (Pattern::Blue, (0, 0, 255)),
+            (Pattern::White, (255, 255, 255)),
+            (Pattern::Black, (0, 0, 0)),
+        ];
+        for (pattern, rgb) in &expected {
+            assert_eq!(pattern.rgb(), Some(*rgb));
+        }
+        assert_eq!(Pattern::Rainbow.rgb(), None);
+        assert_eq!(Pattern::Sinelon1And2.rgb(), None);
+        assert_eq!(Pattern::all().filter(|p| p.rgb().is_some()).count(), 22);
+    }
+
+    #[test]
+    fn try_as_duty_test() {
+        assert_eq!(Pattern::Color1Larson.try_as_duty(u8::MAX), Some(126));
+        assert_eq!(Pattern::Color1Larson.try_as_duty(u16::MAX), Some(32439));
+        assert_eq!(Pattern::Black.try_as_duty(u128::MAX), None);
+    }
+
+    #[test]
+    fn uses_color_test() {
+        let mut color1 = Pattern::all().filter(|p| p.uses_color1());
+        assert_eq!(color1.next(), Some(Pattern::Color1BlendToBlack));
+        assert!(color1.all(|p| p <= Pattern::Color1Strobe || p >= Pattern::Sparkle1On2));
+
+        let mut color2 = Pattern::all().filter(|p| p.uses_color2());
+        assert_eq!(color2.next(), Some(Pattern::Color2BlendToBlack));
+        assert_eq!(color2.next_back(), Some(Pattern::Sinelon1And2));
+
+        assert_eq!(Pattern::all().filter(|p| p.uses_color1()).count(), 20);
+        assert_eq!(Pattern::all().filter(|p| p.uses_color2()).count(), 20);
+        assert!(Pattern::Gradient1And2.uses_color1() && Pattern::Gradient1And2.uses_color2());
+        assert!(!Pattern::Color1Chase.uses_color2());
+        assert!(!Pattern::Color2Chase.uses_color1());
+        assert!(!Pattern::Red.uses_color1() && !Pattern::Red.uses_color2());
+    }
+
+    #[test]
+    fn is_solid_test() {
+        assert!(Pattern::HotPink.is_solid());
+        assert!(Pattern::Black.is_solid());
+        assert!(!Pattern::Sinelon1And2.is_solid());
+        assert!(Pattern::Sinelon1And2.is_animated());
+        assert!(Pattern::all().all(|p| p.is_solid() == (p.category() == Category::SolidColor)));
+    }
+
+    #[test]
+    fn count_test() {
+        assert_eq!(Pattern::COUNT, Pattern::all().count());
+        assert_eq!(Pattern::COUNT, (0..=u8::MAX).filter(|v| Pattern::try_from(*v).is_ok()).count());
+    }
+
+    #[test]
+    fn as_duty_rounded_test() {
+        assert_eq!(Pattern::Color1Larson.as_duty_rounded(u8::MAX, RoundingMode::Nearest), 126);
+        assert_eq!(Pattern::Color1Larson.as_duty_rounded(u8::MAX, RoundingMode::Floor), 126);
+        assert_eq!(Pattern::Color1Larson.as_duty_rounded(u8::MAX, RoundingMode::Ceil), 127);
+
+        assert_eq!(Pattern::Color1Chase.as_duty(u8::MAX), 128);
+        assert_eq!(Pattern::Color1Chase.as_duty_rounded(u8::MAX, RoundingMode::Nearest), 129);
+
+        assert_eq!(Pattern::Black.as_duty_rounded(0u8, RoundingMode::Ceil), 0);
+        assert_eq!(Pattern::Black.as_duty_rounded(-255i32, RoundingMode::Nearest), 0);
+        assert_eq!(Pattern::Black.as_duty_rounded(1u8, RoundingMode::Ceil), 1);
+        assert_eq!(Pattern::Black.as_duty_rounded(1.0f32, RoundingMode::Ceil), 1.0);
+        for pattern in Pattern::all() {
+            for mode in [RoundingMode::Nearest, RoundingMode::Floor, RoundingMode::Ceil] {
+                let duty = pattern.try_as_duty_rounded(u32::MAX, mode);
+                assert!(duty.is_some(), "{:?} {:?}", pattern, mode);
+                assert_eq!(pattern.try_as_duty_rounded(-1i32, mode), Some(0));
+            }
+        }
+    }
+
+    #[cfg(feature = "eh1")]
+    #[test]
+    fn apply_test() {
+        struct MockPin(u16);
+        impl eh1::pwm::ErrorType for MockPin {
+            type Error = core::convert::Infallible;
+        }
+        impl eh1::pwm::SetDutyCycle for MockPin {
+            fn max_duty_cycle(&self) -> u16 {
+                return u8::MAX as u16;
+            }
+            fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+                self.0 = duty;
+                return Ok(());
+            }
+        }
+
+        let mut pin = MockPin(0);
+        Pattern::Color1Larson.apply(&mut pin).unwrap();
+        assert_eq!(pin.0, 126);
+    }
+
+    #[cfg(feature = "eh02")]
+    #[test]
+    fn apply_02_test() {
+        struct MockPin(u16);
+        impl eh02::PwmPin for MockPin {
+            type Duty = u16;
+            fn disable(&mut self) {}
+            fn enable(&mut self) {}
+            fn get_duty(&self) -> u16 {
+                return self.0;
+            }
+            fn get_max_duty(&self) -> u16 {
+                return u8::MAX as u16;
+            }
+            fn set_duty(&mut self, duty: u16) {
+                self.0 = duty;
+            }
+        }
+
+        let mut pin = MockPin(0);
+        Pattern::Color1Larson.apply_02(&mut pin);
+        assert_eq!(pin.0, 126);
+    }
+
+    #[cfg(feature = "eh02")]
+    #[test]
+    fn apply_channel_test() {
+        struct MockPwm([u16; 4]);
+        impl eh02::Pwm for MockPwm {
+            type Channel = usize;
+            type Time = u32;
+            type Duty = u16;
+            fn disable(&mut self, _channel: usize) {}
+            fn enable(&mut self, _channel: usize) {}
+            fn get_period(&self) -> u32 {
+                return 20_000;
+            }
+            fn get_duty(&self, channel: usize) -> u16 {
+                return self.0[channel];
+            }
+            fn get_max_duty(&self) -> u16 {
+                return u8::MAX as u16;
+            }
+            fn set_duty(&mut self, channel: usize, duty: u16) {
+                self.0[channel] = duty;
+            }
+            fn set_period<P: Into<u32>>(&mut self, _period: P) {}
+        }
+
+        let mut pwm = MockPwm([0; 4]);
+        Pattern::Color1Larson.apply_channel(&mut pwm, 2);
+        assert_eq!(pwm.0, [0, 0, 126, 0]);
+    }
+
+    #[test]
+    fn from_pulse_width_us_test() {
+        assert_eq!(Pattern::from_pulse_width_us(1005), Some(Pattern::Rainbow));
+        assert_eq!(Pattern::from_pulse_width_us(1000), Some(Pattern::Rainbow));
+        assert_eq!(Pattern::from_pulse_width_us(1009), Some(Pattern::Rainbow));
+        assert_eq!(Pattern::from_pulse_width_us(1010), Some(Pattern::RainbowParty));
+        assert_eq!(Pattern::from_pulse_width_us(1997), Some(Pattern::Black));
+        // 1160..=1169µs is the slot of the undefined raw value 33
+        assert_eq!(Pattern::from_pulse_width_us(1160), Some(Pattern::BpmRainbow));
+        assert_eq!(Pattern::from_pulse_width_us(1164), Some(Pattern::BpmRainbow));
+        assert_eq!(Pattern::from_pulse_width_us(1165), Some(Pattern::BpmOcean));
+        assert_eq!(Pattern::from_pulse_width_us(1169), Some(Pattern::BpmOcean));
+        for us in 1000..2000 {
+            let raw = (((us - 1000) / 10) * 2) + 1;
+            if let Ok(pattern) = Pattern::try_from(raw as u8) {
+                assert_eq!(Pattern::from_pulse_width_us(us), Some(pattern), "{}", us);
+            }
+        }
+        assert_eq!(Pattern::from_pulse_width_us(999), None);
+        assert_eq!(Pattern::from_pulse_width_us(2000), None);
+        for pattern in Pattern::all() {
+            assert_eq!(Pattern::from_pulse_width_us(pattern.as_pulse_width_us()), Some(pattern));
+        }
+    }
+
+    #[test]
+    fn as_pulse_width_ns_test() {
+        assert_eq!(Pattern::Rainbow.as_pulse_width_ns(), 1_005_000);
+        assert_eq!(Pattern::Black.as_pulse_width_ns(), 1_995_000);
+        for pattern in &[Pattern::Confetti, Pattern::Color2Shot, Pattern::Gold] {
+            assert_eq!(pattern.as_pulse_width_ns(), pattern.as_pulse_width_us() as u32 * 1000);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn compact_pattern_test() {
+        assert_eq!(serde_json::to_string(&CompactPattern(Pattern::Red)).unwrap(), "161");
+        assert_eq!(
+            serde_json::from_str::<CompactPattern>("1").unwrap(),
+            CompactPattern(Pattern::Rainbow)
+        );
+        assert!(serde_json::from_str::<CompactPattern>("2").is_err());
+        assert!(serde_json::from_str::<CompactPattern>("256").is_err());
+    }
+
+    #[test]
+    fn all_slice_test() {
+        assert_eq!(Pattern::ALL.len(), Pattern::COUNT);
+        assert_eq!(Pattern::ALL[0], Pattern::Rainbow);
+        assert_eq!(Pattern::ALL[Pattern::COUNT - 1], Pattern::Black);
+        assert!(Pattern::ALL.iter().copied().eq(Pattern::all()));
+    }
+
+    #[test]
+    fn nearest_solid_color_test() {
+        assert_eq!(Pattern::nearest_solid_color(255, 0, 0), Pattern::Red);
+        assert_eq!(Pattern::nearest_solid_color(250, 10, 5), Pattern::Red);
+        assert_eq!(Pattern::nearest_solid_color(0, 0, 0), Pattern::Black);
+        assert_eq!(Pattern::nearest_solid_color(10, 240, 250), Pattern::Aqua);
+        for pattern in Pattern::all() {
+            if let Some((r, g, b)) = pattern.rgb() {
+                assert_eq!(Pattern::nearest_solid_color(r, g, b), pattern);
+            }
+        }
+    }
+
+    #[test]
+    fn as_duty_clamped_test() {
+        assert_eq!(Pattern::White.as_duty(u16::MAX), 63241);
+        for max_duty in &[1u32, 3, 255, 65534, 16_777_219, u32::MAX - 128] {
+            assert!(Pattern::all().all(|p| p.as_duty(*max_duty) <= *max_duty));
+        }
+    }
+
+    #[test]
+    fn is_off_test() {
+        assert!(Pattern::Black.is_off());
+        assert!(!Pattern::DarkGray.is_off());
+        assert_eq!(Pattern::all().filter(|p| p.is_off()).count(), 1);
+    }
+
+    #[test]
+    fn palette_test() {
+        assert_eq!(Pattern::Rainbow.palette(), Palette::Rainbow);
+        assert_eq!(Pattern::BpmOcean.palette(), Palette::Ocean);
+        assert_eq!(Pattern::WavesForest.palette(), Palette::Forest);
+        assert_eq!(Pattern::RainbowGlitter.palette(), Palette::None);
+        assert_eq!(Pattern::Red.palette(), Palette::None);
+        assert_eq!(Pattern::all().filter(|p| p.palette() == Palette::Party).count(), 4);
+
+        assert_eq!(
+            Pattern::with_effect_and_palette(Effect::Sinelon, Palette::Ocean),
+            Some(Pattern::SinelonOcean)
+        );
+        assert_eq!(Pattern::with_effect_and_palette(Effect::Bpm, Palette::Party), None);
+        assert_eq!(Pattern::with_effect_and_palette(Effect::Chase, Palette::Lava), None);
+    }
+
+    #[test]
+    fn effect_test() {
+        let sinelon = [
+            Pattern::SinelonRainbow,
+            Pattern::SinelonParty,
+            Pattern::SinelonOcean,
+            Pattern::SinelonLava,
+            Pattern::SinelonForest,
+            Pattern::Sinelon1And2,
+        ];
+        assert!(sinelon.iter().all(|p| p.effect() == Effect::Sinelon));
+        assert_eq!(Pattern::all().filter(|p| p.effect() == Effect::Sinelon).count(), sinelon.len());
+
+        assert_eq!(Pattern::Rainbow.effect(), Effect::Rainbow);
+        assert_eq!(Pattern::Color2Larson.effect(), Effect::Larson);
+        assert_eq!(Pattern::Color1HeartbeatFast.effect(), Effect::Heartbeat);
+        assert_eq!(Pattern::EndBlend.effect(), Effect::Blend);
+        assert_eq!(Pattern::Aqua.effect(), Effect::Solid);
+        assert!(Pattern::all().filter(|p| p.is_solid()).all(|p| p.effect() == Effect::Solid));
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn name_test() {
+        assert_eq!(Pattern::RainbowForest.name(), "RainbowForest");
+        assert_eq!(Pattern::RainbowForest.display_name(), "Rainbow, Forest Palette");
+        assert_eq!(Pattern::HotPink.display_name(), "Hot Pink");
+        for pattern in Pattern::all() {
+            assert_eq!(pattern.name().parse::<Pattern>(), Ok(pattern));
+        }
+    }
+
+    #[test]
+    fn closest_to_duty_test() {
+        for pattern in Pattern::all() {
+            assert_eq!(Pattern::closest_to_duty(pattern.as_duty(u16::MAX), u16::MAX), pattern);
+        }
+        assert_eq!(Pattern::closest_to_duty(126u8, u8::MAX), Pattern::Color1Larson);
+        assert_eq!(Pattern::closest_to_duty(0u8, u8::MAX), Pattern::Rainbow);
+        assert_eq!(Pattern::closest_to_duty(u8::MAX, u8::MAX), Pattern::Black);
+        assert_eq!(Pattern::closest_to_duty(500u16, 1000), Pattern::Color1Chase);
+        assert_eq!(Pattern::closest_to_duty(5u16, 0), Pattern::Rainbow);
+    }
+
+    #[test]
+    fn as_duty_zero_max_test() {
+        assert_eq!(Pattern::Black.as_duty::<u16>(0), 0);
+        assert_eq!(Pattern::Black.try_as_duty::<u16>(0), Some(0));
+    }
+
+    #[test]
+    fn required_color_slots_test() {
+        assert_eq!(Pattern::Color1Chase.required_color_slots(), &[ColorSlot::Color1]);
+        assert_eq!(Pattern::Color2Shot.required_color_slots(), &[ColorSlot::Color2]);
+        assert_eq!(
+            Pattern::Gradient1And2.required_color_slots(),
+            &[ColorSlot::Color1, ColorSlot::Color2]
+        );
+        assert_eq!(Pattern::Rainbow.required_color_slots(), &[]);
+
+        assert!(Pattern::Gradient1And2.requires_color_setup());
+        assert!(!Pattern::Red.requires_color_setup());
+        assert_eq!(Pattern::all().filter(|p| p.requires_color_setup()).count(), 30);
+    }
+
+    #[test]
+    fn try_from_wide_test() {
+        assert_eq!(Pattern::try_from(161u16), Ok(Pattern::Red));
+        assert_eq!(Pattern::try_from(161i32), Ok(Pattern::Red));
+        assert_eq!(Pattern::try_from(256u16), Err(InvalidPatternValue(256u16)));
+        assert_eq!(Pattern::try_from(256i32), Err(InvalidPatternValue(256i32)));
+        assert_eq!(Pattern::try_from(-1i32), Err(InvalidPatternValue(-1i32)));
+        assert_eq!(Pattern::try_from(160i32), Err(InvalidPatternValue(160i32)));
+    }
+
+    #[test]
+    fn as_duty_scaled_test() {
+        assert_eq!(Pattern::Color1Larson.as_duty_scaled(u8::MAX, 0.5), 63);
+        assert_eq!(Pattern::Color1Larson.as_duty_scaled(u8::MAX, 1.0), 126);
+        assert_eq!(Pattern::Color1Larson.as_duty_scaled(u8::MAX, 2.0), 126);
+        assert_eq!(Pattern::Color1Larson.as_duty_scaled(u8::MAX, -1.0), 0);
+    }
+
+    #[test]
+    fn solids_test() {
+        assert_eq!(Pattern::solids().count(), 22);
+        assert_eq!(Pattern::solids().next(), Some(Pattern::HotPink));
+        assert!(Pattern::solids().all(|p| p.is_solid()));
+
+        let mut chase = Pattern::in_category(Category::Chase);
+        assert_eq!(chase.next(), Some(Pattern::ChaseRed));
+        assert_eq!(chase.next(), Some(Pattern::ChaseBlue));
+        assert_eq!(chase.next(), Some(Pattern::ChaseGray));
+        assert_eq!(chase.next(), None);
+    }
+
+    #[test]
+    fn hsv_test() {
+        assert_eq!(Pattern::Red.hsv(), Some((0.0, 1.0, 1.0)));
+        assert_eq!(Pattern::Lime.hsv(), Some((120.0, 1.0, 1.0)));
+        assert_eq!(Pattern::Blue.hsv(), Some((240.0, 1.0, 1.0)));
+        assert_eq!(Pattern::Black.hsv(), Some((0.0, 0.0, 0.0)));
+        assert_eq!(Pattern::Rainbow.hsv(), None);
+
+        let (hue, saturation, _) = Pattern::HotPink.hsv().unwrap();
+        assert!((hue - 330.0).abs() < 0.5);
+        assert!(saturation < 1.0);
+    }
+
+    #[test]
+    fn spark_set_value_test() {
+        assert_eq!(Pattern::FireMedium.as_spark_set_value(), -0.59);
+        assert_eq!(Pattern::Aqua.as_spark_set_value(), 0.81);
+        assert_eq!(Pattern::from_spark_set_value(0.81), Some(Pattern::Aqua));
+        assert_eq!(Pattern::from_spark_set_value(-0.99), Some(Pattern::Rainbow));
+        assert_eq!(Pattern::from_spark_set_value(1.5), Some(Pattern::Black));
+    }
+
+    #[test]
+    fn raw_values_test() {
+        for (i, pattern) in Pattern::all().enumerate() {
+            let raw = pattern.as_raw();
+            assert!(raw % 2 == 1, "{:?} has an even raw value ({})", pattern, raw);
+            assert!((1..=199).contains(&raw), "{:?} is out of range ({})", pattern, raw);
+            assert!(
+                Pattern::all().skip(i + 1).all(|other| other.as_raw() != raw),
+                "{:?} shares its raw value ({}) with another pattern",
+                pattern,
+                raw
+            );
+        }
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn playlist_test() {
+        let mut playlist: Playlist<3> = Playlist::new();
+        assert_eq!(playlist.advance(0), None);
+
+        playlist.push(Pattern::Red, 1000).unwrap();
+        playlist.push(Pattern::Blue, 500).unwrap();
+        playlist.push(Pattern::Rainbow, 2000).unwrap();
+        assert!(playlist.push(Pattern::Black, 100).is_err());
+
+        assert_eq!(playlist.advance(0), Some(Pattern::Red));
+        assert_eq!(playlist.advance(999), Some(Pattern::Red));
+        assert_eq!(playlist.advance(1), Some(Pattern::Blue));
+        assert_eq!(playlist.advance(500), Some(Pattern::Rainbow));
+        assert_eq!(playlist.advance(1999), Some(Pattern::Rainbow));
+        assert_eq!(playlist.advance(1), Some(Pattern::Red));
+        assert_eq!(playlist.advance(3500 * 4 + 1200), Some(Pattern::Blue));
+
+        playlist.reset();
+        assert_eq!(playlist.advance(0), Some(Pattern::Red));
+    }
+
+    #[test]
+    fn rgb_gamma_test() {
+        assert_eq!(Pattern::Gray.rgb_gamma(1.0), Pattern::Gray.rgb());
+        assert_eq!(Pattern::Gray.rgb_gamma(2.2), Some((56, 56, 56)));
+        assert_eq!(Pattern::White.rgb_gamma(2.2), Some((255, 255, 255)));
+        assert_eq!(Pattern::Black.rgb_gamma(2.2), Some((0, 0, 0)));
+        assert_eq!(Pattern::Rainbow.rgb_gamma(2.2), None);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_test() {
+        struct XorShift(u32);
+        impl rand_core::RngCore for XorShift {
+            fn next_u32(&mut self) -> u32 {
+                self.0 ^= self.0 << 13;
+                self.0 ^= self.0 >> 17;
+                self.0 ^= self.0 << 5;
+                return self.0;
+            }
+            fn next_u64(&mut self) -> u64 {
+                return rand_core::impls::next_u64_via_u32(self);
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                rand_core::impls::fill_bytes_via_next(self, dest);
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                self.fill_bytes(dest);
+                return Ok(());
+            }
+        }
+
+        let mut rng = XorShift(0x1105);
+        let mut seen = [false; 256];
+        for _ in 0..10_000 {
+            let pattern = Pattern::random(&mut rng);
+            assert_eq!(Pattern::try_from(pattern.as_raw()), Ok(pattern));
+            seen[pattern.as_raw() as usize] = true;
+        }
+        assert_eq!(seen.iter().filter(|seen| **seen).count(), Pattern::COUNT);
+
+        for _ in 0..100 {
+            assert_eq!(Pattern::random_in(Category::Chase, &mut rng).category(), Category::Chase);
+        }
+    }
+
+    #[test]
+    fn as_duty_int_test() {
+        assert_eq!(Pattern::Color1Larson.as_duty_int(u8::MAX as u32), 126);
+        assert_eq!(Pattern::Black.as_duty_int(u32::MAX), 4_273_492_458);
+        for pattern in Pattern::all() {
+            let float = pattern.as_duty(u16::MAX) as i64;
+            let int = pattern.as_duty_int(u16::MAX as u32) as i64;
+            assert!((float - int).abs() <= 1, "{:?}: {} != {}", pattern, float, int);
+        }
+    }
+
+    #[test]
+    fn checked_next_prev_test() {
+        assert_eq!(Pattern::Rainbow.checked_next(), Some(Pattern::RainbowParty));
+        assert_eq!(Pattern::Black.checked_prev(), Some(Pattern::DarkGray));
+        assert_eq!(Pattern::Black.checked_next(), None);
+        assert_eq!(Pattern::Rainbow.checked_prev(), None);
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn info_test() {
+        let info = Pattern::Red.info();
+        assert_eq!(info.name, "Red");
+        assert_eq!(info.display_name, "Red");
+        assert_eq!(info.raw, 161);
+        assert_eq!(info.pulse_width_us, 1805);
+
+        assert_eq!(Pattern::TABLE.len(), Pattern::COUNT);
+        for (info, pattern) in Pattern::TABLE.iter().zip(Pattern::all()) {
+            assert_eq!(info.pattern, pattern);
+            assert_eq!(info.pulse_width_us, pattern.as_pulse_width_us());
+            assert_eq!(info.display_name, pattern.display_name());
+        }
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn parse_suggestion_test() {
+        let suggest = |s: &str| s.parse::<Pattern>().unwrap_err().suggestion();
+        assert_eq!(suggest("RainbowForrest"), Some("RainbowForest"));
+        assert_eq!(suggest("rainbowforest"), Some("RainbowForest"));
+        assert_eq!(suggest("HotPnk"), Some("HotPink"));
+        assert_eq!(suggest("Plaid"), None);
+        assert_eq!(suggest("ThisIsAVeryLongStringThatIsNotAPatternName"), None);
+    }
+
+    #[test]
+    fn is_two_color_test() {
+        let expected = [
+            Pattern::Sparkle1On2,
+            Pattern::Sparkle2On1,
+            Pattern::Gradient1And2,
+            Pattern::Bpm1And2,
+            Pattern::EndBlend1And2,
+            Pattern::EndBlend,
+            Pattern::Color1And2NoBlend,
+            Pattern::Twinkle1And2,
+            Pattern::Waves1And2,
+            Pattern::Sinelon1And2,
+        ];
+        assert!(Pattern::all().filter(|p| p.is_two_color()).eq(expected.iter().copied()));
+        assert!(!Pattern::Color1Chase.is_two_color());
+    }
+
+    #[test]
+    fn raw_values_table_test() {
+        assert!(Pattern::RAW_VALUES.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(Pattern::RAW_VALUES.iter().copied().eq(Pattern::all().map(|p| p.as_raw())));
+    }
+
+    #[test]
+    fn step_by_test() {
+        assert_eq!(Pattern::Rainbow.step_by(0), Pattern::Rainbow);
+        assert_eq!(Pattern::Rainbow.step_by(1), Pattern::RainbowParty);
+        assert_eq!(Pattern::Rainbow.step_by(-1), Pattern::Black);
+        assert_eq!(Pattern::Black.step_by(1), Pattern::Rainbow);
+        assert_eq!(Pattern::Rainbow.step_by(Pattern::COUNT as i32), Pattern::Rainbow);
+
+        let mut stepped = Pattern::Aqua;
+        for _ in 0..200 {
+            stepped = stepped.next();
+        }
+        assert_eq!(Pattern::Aqua.step_by(200), stepped);
+        assert_eq!(Pattern::Aqua.step_by(-200).step_by(200), Pattern::Aqua);
+        assert_eq!(Pattern::Aqua.step_by(i32::MIN).step_by(i32::MAX).next(), Pattern::Aqua);
+    }
+
+    #[test]
+    fn as_blinkin_frame_test() {
+        assert_eq!(Pattern::Rainbow.as_blinkin_frame(), (1005, 20_000));
+        for pattern in Pattern::all() {
+            let (pulse_width_us, period_us) = pattern.as_blinkin_frame();
+            assert_eq!(pulse_width_us, pattern.as_pulse_width_us());
+            assert_eq!(period_us, 20_000);
+        }
+    }
+
+    #[test]
+    fn as_duty_signed_test() {
+        assert_eq!(Pattern::Color1Larson.as_duty(u8::MAX as i32), 126);
+        assert_eq!(Pattern::Color1Larson.as_duty(i16::MAX), 16219);
+        assert!(Pattern::all().all(|p| (0..=i32::MAX).contains(&p.as_duty(i32::MAX))));
+        assert_eq!(Pattern::Black.as_duty(-100i32), 0);
+    }
+
+    #[test]
+    fn contrast_color_test() {
+        assert_eq!(Pattern::White.contrast_color(), Some((0, 0, 0)));
+        assert_eq!(Pattern::Yellow.contrast_color(), Some((0, 0, 0)));
+        assert_eq!(Pattern::DarkBlue.contrast_color(), Some((255, 255, 255)));
+        assert_eq!(Pattern::Black.contrast_color(), Some((255, 255, 255)));
+        assert_eq!(Pattern::Rainbow.contrast_color(), None);
+    }
+
+    #[test]
+    fn cycle_from_test() {
+        let mut cycle = Pattern::cycle_from(Pattern::Gray).take(Pattern::COUNT + 2);
+        assert_eq!(cycle.next(), Some(Pattern::Gray));
+        assert_eq!(cycle.next(), Some(Pattern::DarkGray));
+        assert_eq!(cycle.next(), Some(Pattern::Black));
+        assert_eq!(cycle.next(), Some(Pattern::Rainbow));
+        assert_eq!(cycle.last(), Some(Pattern::DarkGray));
+    }
+
+    #[test]
+    fn is_valid_raw_test() {
+        assert!(Pattern::is_valid_raw(1));
+        assert!(Pattern::is_valid_raw(99));
+        assert!(!Pattern::is_valid_raw(0));
+        assert!(!Pattern::is_valid_raw(33));
+        assert!(!Pattern::is_valid_raw(89));
+        assert!(!Pattern::is_valid_raw(201));
+        for value in 0..=u8::MAX {
+            assert_eq!(Pattern::is_valid_raw(value), Pattern::try_from(value).is_ok(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn first_last_test() {
+        assert_eq!(Pattern::FIRST, Pattern::Rainbow);
+        assert_eq!(Pattern::LAST, Pattern::Black);
+        assert_eq!(Pattern::all().min(), Some(Pattern::FIRST));
+        assert_eq!(Pattern::all().max(), Some(Pattern::LAST));
+        assert_eq!(Pattern::LAST.checked_next(), None);
+        assert_eq!(Pattern::FIRST.checked_prev(), None);
+    }
+
+    #[cfg(feature = "fugit")]
+    #[test]
+    fn pulse_width_test() {
+        assert_eq!(Pattern::Black.pulse_width(), fugit::MicrosDurationU32::micros(1995));
+        assert_eq!(Pattern::Black.pulse_width().to_nanos(), Pattern::Black.as_pulse_width_ns());
+    }
+
+    #[test]
+    fn blend_toward_test() {
+        assert_eq!(Pattern::Red.blend_toward(Pattern::Yellow, 0.0), Pattern::Red);
+        assert_eq!(Pattern::Red.blend_toward(Pattern::Yellow, 1.0), Pattern::Yellow);
+        assert_eq!(Pattern::Red.blend_toward(Pattern::Yellow, 0.5), Pattern::Orange);
+        assert_eq!(Pattern::Yellow.blend_toward(Pattern::Red, 0.5), Pattern::Orange);
+        assert_eq!(Pattern::Red.blend_toward(Pattern::Yellow, 2.0), Pattern::Yellow);
+        assert_eq!(Pattern::BpmRainbow.blend_toward(Pattern::BpmOcean, 0.5), Pattern::BpmOcean);
+    }
+
+    #[test]
+    fn index_test() {
+        assert_eq!(Pattern::Rainbow.index(), 0);
+        assert_eq!(Pattern::BpmOcean.index(), 16);
+        assert_eq!(Pattern::Black.index(), Pattern::COUNT - 1);
+        assert_eq!(Pattern::from_index(Pattern::COUNT), None);
+        for pattern in Pattern::all() {
+            assert_eq!(Pattern::ALL[pattern.index()], pattern);
+            assert_eq!(Pattern::from_index(pattern.index()), Some(pattern));
+        }
+    }
+
+    #[test]
+    fn from_raw_test() {
+        const RED: Pattern = match Pattern::from_raw(161) {
+            Some(pattern) => pattern,
+            None => Pattern::Black,
+        };
+        const _: u8 = RED.as_raw();
+        assert_eq!(RED, Pattern::Red);
+        assert_eq!(Pattern::from_raw(0), None);
+        assert_eq!(Pattern::from_raw(33), None);
+        for pattern in Pattern::all() {
+            assert_eq!(Pattern::from_raw(pattern.as_raw()), Some(pattern));
+        }
+    }
+
+    #[test]
+    fn is_one_shot_test() {
+        let shots = [
+            Pattern::RedShot,
+            Pattern::BlueShot,
+            Pattern::WhiteShot,
+            Pattern::Color1Shot,
+            Pattern::Color2Shot,
+        ];
+        for pattern in Pattern::all() {
+            assert_eq!(pattern.is_one_shot(), shots.contains(&pattern));
+        }
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn try_from_str_test() {
+        assert_eq!(Pattern::try_from("RainbowForest"), Ok(Pattern::RainbowForest));
+        assert_eq!(
+            Pattern::try_from("Rainbow, Forest Palette"),
+            Ok(Pattern::RainbowForest)
+        );
+        assert_eq!(Pattern::try_from("  Shot, Red\n"), Ok(Pattern::RedShot));
+        assert_eq!(Pattern::try_from("\tHotPink "), Ok(Pattern::HotPink));
+        assert!(Pattern::try_from("Rainbow Forest Palette").is_err());
+        for pattern in Pattern::all() {
+            assert_eq!(Pattern::try_from(pattern.name()), Ok(pattern));
+            assert_eq!(Pattern::try_from(pattern.display_name()), Ok(pattern));
+        }
+    }
+
+    #[test]
+    fn as_compare_value_test() {
+        // 50Hz with a 1MHz timer, so one tick per microsecond
+        assert_eq!(Pattern::Rainbow.as_compare_value(20_000, 20_000), 1005);
+        assert_eq!(Pattern::Black.as_compare_value(20_000, 20_000), 1995);
+        // 50Hz with a 16-bit timer
+        assert_eq!(Pattern::Rainbow.as_compare_value(65_535, 20_000), 3293);
+        assert_eq!(Pattern::Red.as_compare_value(u32::MAX, 20_000), 387_620_798);
+        // Pulse width longer than the period
+        assert_eq!(Pattern::Black.as_compare_value(1000, 1500), 1000);
+        assert_eq!(Pattern::Black.as_compare_value(1000, 0), 1000);
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn write_table_test() {
+        let mut table: heapless::String<4096> = heapless::String::new();
+        Pattern::write_table(&mut table).unwrap();
+        let mut lines = table.lines();
+        assert_eq!(lines.next(), Some("Rainbow,1,-0.99,1005"));
+        assert!(table.contains("\nFireMedium,41,-0.59,1205\n"));
+        assert_eq!(table.lines().last(), Some("Black,199,0.99,1995"));
+        assert_eq!(table.lines().count(), Pattern::COUNT);
+    }
+
+    #[test]
+    fn similar_in_palette_test() {
+        assert_eq!(
+            Pattern::SinelonOcean.similar_in_palette(Palette::Forest),
+            Some(Pattern::SinelonForest)
+        );
+        assert_eq!(
+            Pattern::BpmOcean.similar_in_palette(Palette::Rainbow),
+            Some(Pattern::BpmRainbow)
+        );
+        assert_eq!(Pattern::BpmOcean.similar_in_palette(Palette::Party), None);
+        assert_eq!(Pattern::Confetti.similar_in_palette(Palette::Lava), None);
+        assert_eq!(
+            Pattern::BlueShot.similar_in_palette(Palette::None),
+            Some(Pattern::BlueShot)
+        );
+    }
+
+    #[test]
+    fn from_abs_percentage_test() {
+        assert_eq!(Pattern::from_abs_percentage(0.0), Some(Pattern::Rainbow));
+        assert_eq!(Pattern::from_abs_percentage(1.0), Some(Pattern::Black));
+        assert_eq!(Pattern::from_abs_percentage(0.5), Some(Pattern::Color1Chase));
+        // Raw 33 is a gap, equally far from BpmRainbow (31) and BpmOcean (35)
+        assert_eq!(Pattern::from_abs_percentage(0.165), Some(Pattern::BpmOcean));
+        assert_eq!(Pattern::from_abs_percentage(-0.01), None);
+        assert_eq!(Pattern::from_abs_percentage(1.01), None);
+        assert_eq!(Pattern::from_abs_percentage(f32::NAN), None);
+        for pattern in Pattern::all() {
+            assert_eq!(
+                Pattern::from_abs_percentage(pattern.as_abs_percentage()),
+                Some(pattern)
+            );
+        }
+    }
+
+    #[test]
+    fn from_raw_clamped_test() {
+        assert_eq!(Pattern::from_raw_clamped(161), Pattern::Red);
+        assert_eq!(Pattern::from_raw_clamped(160), Pattern::Red);
+        assert_eq!(Pattern::from_raw_clamped(89), Pattern::StrobeBlue);
+        assert_eq!(Pattern::from_raw_clamped(0), Pattern::Rainbow);
+        assert_eq!(Pattern::from_raw_clamped(200), Pattern::Black);
+        assert_eq!(Pattern::from_raw_clamped(u8::MAX), Pattern::Black);
+    }
+
+    #[test]
+    fn add_sub_test() {
+        assert_eq!(Pattern::Rainbow + 1, Pattern::RainbowParty);
+        assert_eq!(Pattern::Rainbow - 1, Pattern::Black);
+        assert_eq!(Pattern::Black + 1, Pattern::Rainbow);
+        assert_eq!(Pattern::Rainbow + -1, Pattern::Black);
+        assert_eq!(Pattern::Rainbow - -1, Pattern::RainbowParty);
+        assert_eq!(Pattern::Red + Pattern::COUNT as i32, Pattern::Red);
+        assert_eq!((Pattern::Red - i32::MIN) + i32::MIN, Pattern::Red);
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn describe_test() {
+        assert_eq!(
+            Pattern::LarsonRed.describe(),
+            "Red single-LED bounce (Larson scanner)"
+        );
+        for pattern in Pattern::all() {
+            assert!(!pattern.describe().is_empty());
+        }
+    }
+
+    #[test]
+    fn is_strobe_test() {
+        let strobes = [
+            Pattern::StrobeBlue,
+            Pattern::StrobeGold,
+            Pattern::StrobeWhite,
+            Pattern::Color1Strobe,
+            Pattern::Color2Strobe,
+        ];
+        for pattern in Pattern::all() {
+            assert_eq!(pattern.is_strobe(), strobes.contains(&pattern));
+        }
+    }
+
+    #[test]
+    fn nearest_valid_pulse_width_us_test() {
+        assert_eq!(Pattern::nearest_valid_pulse_width_us(1805), (Pattern::Red, 0));
+        assert_eq!(Pattern::nearest_valid_pulse_width_us(1808), (Pattern::Red, 3));
+        assert_eq!(Pattern::nearest_valid_pulse_width_us(1801), (Pattern::Red, -4));
+        assert_eq!(Pattern::nearest_valid_pulse_width_us(0), (Pattern::Rainbow, -1005));
+        assert_eq!(Pattern::nearest_valid_pulse_width_us(u16::MAX), (Pattern::Black, i16::MAX));
+    }
+
+    #[test]
+    fn from_pattern_for_integers_test() {
+        let raw_u8: u8 = Pattern::Red.into();
+        let raw_u16: u16 = Pattern::Red.into();
+        let raw_u32: u32 = Pattern::Black.into();
+        assert_eq!(raw_u8, 161);
+        assert_eq!(raw_u16, 161);
+        assert_eq!(raw_u32, 199);
+    }
+
+    #[test]
+    fn pattern_byte_test() {
+        let byte = PatternByte::new(161).unwrap();
+        assert_eq!(byte.get(), 161);
+        assert_eq!(byte.pattern(), Pattern::Red);
+        assert_eq!(PatternByte::new(33), None);
+        assert_eq!(PatternByte::new(160), None);
+        assert_eq!(PatternByte::new(200), None);
+        assert_eq!(core::mem::size_of::<PatternByte>(), 1);
+        for pattern in Pattern::all() {
+            assert_eq!(PatternByte::from(pattern).pattern(), pattern);
+        }
+    }
+
+    #[test]
+    fn sweep_test() {
+        let mut sweep = Pattern::sweep();
+        assert_eq!(sweep.next(), Some((Pattern::Rainbow, 1005)));
+        assert_eq!(sweep.last(), Some((Pattern::Black, 1995)));
+        let mut previous = 0;
+        for (pattern, pulse_width_us) in Pattern::sweep() {
+            assert_eq!(pulse_width_us, pattern.as_pulse_width_us());
+            assert!(pulse_width_us > previous);
+            previous = pulse_width_us;
+        }
+        assert_eq!(Pattern::sweep().count(), Pattern::COUNT);
+    }
+
+    #[test]
+    fn luminance_test() {
+        assert!((Pattern::White.luminance().unwrap() - 1.0).abs() < 0.0001);
+        assert_eq!(Pattern::Black.luminance(), Some(0.0));
+        assert!((Pattern::Red.luminance().unwrap() - 0.2126).abs() < 0.0001);
+        assert_eq!(Pattern::Rainbow.luminance(), None);
+        assert!(Pattern::Yellow.luminance() > Pattern::Blue.luminance());
+    }
+
+    #[cfg(all(feature = "heapless", feature = "names"))]
+    #[test]
+    fn to_json_entry_test() {
+        assert_eq!(
+            Pattern::Rainbow.to_json_entry::<48>(),
+            r#"{"name":"Rainbow","value":1}"#
+        );
+        assert_eq!(
+            Pattern::Color1HeartbeatMedium.to_json_entry::<48>(),
+            r#"{"name":"Color1HeartbeatMedium","value":105}"#
+        );
+        let mut longest = 0;
+        for pattern in Pattern::all() {
+            let entry = pattern.to_json_entry::<{ Pattern::JSON_ENTRY_MAX_LEN }>();
+            assert!(entry.contains(pattern.name()));
+            longest = longest.max(entry.len());
+        }
+        assert_eq!(longest, Pattern::JSON_ENTRY_MAX_LEN);
+        assert_eq!(
+            Pattern::Color1HeartbeatMedium.to_json_entry::<64>().len(),
+            Pattern::JSON_ENTRY_MAX_LEN
+        );
+    }
+
+    #[test]
+    fn is_static_preview_test() {
+        assert!(Pattern::Red.is_static_preview());
+        assert!(Pattern::Black.is_static_preview());
+        assert!(Pattern::Gradient1And2.is_static_preview());
+        assert!(Pattern::BreathBlue.is_static_preview());
+        assert!(Pattern::Color1BreathSlow.is_static_preview());
+        assert!(!Pattern::Color1BreathFast.is_static_preview());
+        assert!(!Pattern::Rainbow.is_static_preview());
+        assert!(!Pattern::StrobeWhite.is_static_preview());
+        assert!(!Pattern::FireLarge.is_static_preview());
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn from_name_loose_test() {
+        assert_eq!(Pattern::from_name_loose("rainbow forest"), Some(Pattern::RainbowForest));
+        assert_eq!(Pattern::from_name_loose("RAINBOW_FOREST"), Some(Pattern::RainbowForest));
+        assert_eq!(Pattern::from_name_loose("rainbowforest"), Some(Pattern::RainbowForest));
+        assert_eq!(Pattern::from_name_loose("color-1 shot"), Some(Pattern::Color1Shot));
+        assert_eq!(Pattern::from_name_loose("rainbow"), Some(Pattern::Rainbow));
+        assert_eq!(Pattern::from_name_loose("rainbow forests"), None);
+        assert_eq!(Pattern::from_name_loose(""), None);
+    }
+
+    #[test]
+    fn next_prev_in_category_test() {
+        assert_eq!(Pattern::ChaseRed.next_in_category(), Pattern::ChaseBlue);
+        assert_eq!(Pattern::ChaseGray.next_in_category(), Pattern::ChaseRed);
+        assert_eq!(Pattern::ChaseRed.prev_in_category(), Pattern::ChaseGray);
+        assert_eq!(Pattern::ChaseBlue.prev_in_category(), Pattern::ChaseRed);
+        assert_eq!(Pattern::Black.next_in_category(), Pattern::HotPink);
+        assert_eq!(Pattern::Confetti.next_in_category(), Pattern::Confetti);
+        for pattern in Pattern::all() {
+            assert_eq!(pattern.next_in_category().category(), pattern.category());
+            assert_eq!(pattern.next_in_category().prev_in_category(), pattern);
+        }
+    }
+
+    #[test]
+    fn as_duty_precise_test() {
+        assert_eq!(Pattern::Color1Larson.as_duty_precise(u8::MAX as u64), 126);
+        assert_eq!(Pattern::Red.as_duty_precise(1 << 30), 864_362_168);
+        assert_ne!(Pattern::Red.as_duty(1u64 << 30), 864_362_168);
+        assert_eq!(Pattern::Black.as_duty_precise(u64::MAX), 18_354_510_353_341_003_856);
+        for pattern in Pattern::all() {
+            let int = pattern.as_duty_int(u32::MAX) as u64;
+            assert_eq!(pattern.as_duty_precise(u32::MAX as u64), int);
+        }
+    }
+
+    #[test]
+    fn pattern_toggle_test() {
+        assert_eq!(PatternToggle::new(Pattern::Black), None);
+
+        let mut toggle = PatternToggle::new(Pattern::Red).unwrap();
+        assert_eq!(toggle.current(), Pattern::Red);
+        assert_eq!(toggle.toggle(), Pattern::Black);
+        assert_eq!(toggle.toggle(), Pattern::Red);
+
+        toggle.set(Pattern::Blue);
+        toggle.set(Pattern::Black);
+        assert_eq!(toggle.current(), Pattern::Black);
+        assert_eq!(toggle.toggle(), Pattern::Blue);
+    }
+
+    #[test]
+    fn animation_period_ms_test() {
+        assert!(
+            Pattern::Color1BreathSlow.animation_period_ms()
+                > Pattern::Color1BreathFast.animation_period_ms()
+        );
+        assert!(
+            Pattern::Color2HeartbeatSlow.animation_period_ms()
+                > Pattern::Color2HeartbeatMedium.animation_period_ms()
+        );
+        assert!(
+            Pattern::Color2HeartbeatMedium.animation_period_ms()
+                > Pattern::Color2HeartbeatFast.animation_period_ms()
+        );
+        assert_eq!(Pattern::StrobeGold.animation_period_ms(), Some(100));
+        assert!(Pattern::SinelonOcean.animation_period_ms().is_some());
+        assert_eq!(Pattern::Confetti.animation_period_ms(), None);
+        assert_eq!(Pattern::Red.animation_period_ms(), None);
+    }
+
+    #[test]
+    fn pattern_iter_double_ended_test() {
+        assert_eq!(Pattern::all().next_back(), Some(Pattern::Black));
+        assert_eq!(Pattern::all().rev().nth(1), Some(Pattern::DarkGray));
+        assert_eq!(Pattern::all().len(), Pattern::COUNT);
+
+        let mut all = Pattern::all();
+        assert_eq!(all.next(), Some(Pattern::Rainbow));
+        assert_eq!(all.next_back(), Some(Pattern::Black));
+        assert_eq!(all.len(), Pattern::COUNT - 2);
+    }
+
+    #[test]
+    fn from_numeric_str_test() {
+        assert_eq!(Pattern::from_numeric_str("161"), Some(Pattern::Red));
+        assert_eq!(Pattern::from_numeric_str("1"), Some(Pattern::Rainbow));
+        assert_eq!(Pattern::from_numeric_str("160"), None);
+        assert_eq!(Pattern::from_numeric_str("256"), None);
+        assert_eq!(Pattern::from_numeric_str("-1"), None);
+        assert_eq!(Pattern::from_numeric_str("Red"), None);
+        assert_eq!(Pattern::from_numeric_str(""), None);
+    }
+
+    #[test]
+    fn complement_test() {
+        assert_eq!(Pattern::Red.complement(), Some(Pattern::Aqua));
+        assert_eq!(Pattern::Aqua.complement(), Some(Pattern::Red));
+        assert_eq!(Pattern::Yellow.complement(), Some(Pattern::Blue));
+        assert_eq!(Pattern::Gray.complement(), Some(Pattern::Gray));
+        assert_eq!(Pattern::Black.complement(), Some(Pattern::Black));
+        assert_eq!(Pattern::Rainbow.complement(), None);
+    }
+
+    #[test]
+    fn resolution_adequate_test() {
+        assert!(!Pattern::resolution_adequate(50));
+        assert!(!Pattern::resolution_adequate(99));
+        assert!(Pattern::resolution_adequate(100));
+        assert!(Pattern::resolution_adequate(u16::MAX as u32));
+        for max_duty in 0..300 {
+            let distinct = Pattern::all()
+                .zip(Pattern::all().skip(1))
+                .all(|(a, b)| a.as_duty_int(max_duty) != b.as_duty_int(max_duty));
+            assert_eq!(Pattern::resolution_adequate(max_duty), distinct, "{}", max_duty);
+        }
+    }
+
+    #[test]
+    fn category_first_test() {
+        assert_eq!(Category::Rainbow.first_pattern(), Pattern::Rainbow);
+        assert_eq!(Category::Chase.first_pattern(), Pattern::ChaseRed);
+        assert_eq!(Category::SolidColor.first_pattern(), Pattern::HotPink);
+        assert!(Pattern::Rainbow.is_category_first());
+        assert!(Pattern::HotPink.is_category_first());
+        assert!(!Pattern::RainbowParty.is_category_first());
+        assert!(!Pattern::Black.is_category_first());
+        for pattern in Pattern::all() {
+            assert_eq!(pattern.is_category_first(), pattern.prev_in_category() >= pattern);
+        }
+    }
+
+    #[test]
+    fn hue_ring_test() {
+        let mut ring = Pattern::hue_ring();
+        assert_eq!(ring.next(), Some(Pattern::DarkRed));
+        assert_eq!(ring.next(), Some(Pattern::Red));
+        assert_eq!(Pattern::hue_ring().last(), Some(Pattern::HotPink));
+
+        let mut previous = 0.0;
+        for pattern in Pattern::hue_ring() {
+            assert!(pattern.is_solid());
+            let (hue, saturation, _) = pattern.hsv().unwrap();
+            assert!(saturation > 0.0);
+            assert!(hue >= previous, "{:?} is out of order", pattern);
+            previous = hue;
+        }
+        for excluded in [Pattern::White, Pattern::Gray, Pattern::DarkGray, Pattern::Black] {
+            assert!(Pattern::hue_ring().all(|pattern| pattern != excluded));
+        }
+        assert_eq!(Pattern::hue_ring().count(), 18);
+    }
+
+    #[test]
+    fn safe_startup_test() {
+        assert_eq!(Pattern::safe_startup(), Pattern::Blue);
+        assert_ne!(Pattern::safe_startup(), Pattern::default());
+        assert!(Pattern::safe_startup().is_solid());
+        assert!(!Pattern::safe_startup().is_off());
     }
 }